@@ -85,4 +85,238 @@ pub enum XploraError {
     
     #[msg("Insufficient reward vault balance")]
     InsufficientVaultBalance,
+    
+    #[msg("Invalid profile bio")]
+    InvalidBio,
+    
+    #[msg("Username can only be changed once per cooldown period")]
+    UsernameChangeTooSoon,
+    
+    #[msg("Username record does not match profile")]
+    UsernameRecordMismatch,
+    
+    #[msg("Fee payer is not an allowed relayer")]
+    RelayerNotAllowed,
+    
+    #[msg("Too many relayers (max 5)")]
+    TooManyRelayers,
+    
+    #[msg("Daily sponsored submission limit reached")]
+    SponsoredLimitReached,
+    
+    #[msg("Signer is not the user or a valid session key")]
+    InvalidSessionKey,
+    
+    #[msg("Session key expired")]
+    SessionExpired,
+    
+    #[msg("Invalid session expiry")]
+    InvalidSessionExpiry,
+    
+    #[msg("Session key scope does not allow this action")]
+    SessionScopeNotAllowed,
+    
+    #[msg("Session key not valid for this location")]
+    SessionLocationNotAllowed,
+    
+    #[msg("Invalid evidence count (1 to 4 attachments)")]
+    InvalidEvidenceCount,
+    
+    #[msg("Invalid evidence attachment")]
+    InvalidEvidence,
+    
+    #[msg("Submission location too far from quest")]
+    LocationTooFar,
+    
+    #[msg("GPS accuracy too low")]
+    GpsAccuracyTooLow,
+    
+    #[msg("GPS fix is stale or in the future")]
+    StaleLocationFix,
+    
+    #[msg("Quest requires a location oracle attestation")]
+    LocationAttestationRequired,
+    
+    #[msg("Invalid Ed25519 attestation")]
+    InvalidAttestation,
+    
+    #[msg("AI confidence below approval threshold")]
+    ConfidenceTooLow,
+    
+    #[msg("Invalid AI model version")]
+    InvalidModelVersion,
+    
+    #[msg("Quest requires validator consensus")]
+    ConsensusRequired,
+    
+    #[msg("Quest does not use validator consensus")]
+    ConsensusNotEnabled,
+    
+    #[msg("Validator is not active")]
+    ValidatorNotActive,
+    
+    #[msg("No vote reward available")]
+    NoVoteReward,
+    
+    #[msg("Submission cannot be appealed")]
+    AppealNotAllowed,
+    
+    #[msg("Appeal window has closed")]
+    AppealWindowClosed,
+    
+    #[msg("Submission is not under appeal")]
+    SubmissionNotAppealed,
+    
+    #[msg("Invalid stake amount")]
+    InvalidStakeAmount,
+    
+    #[msg("Insufficient validator stake")]
+    InsufficientStake,
+    
+    #[msg("Unbonding period not complete")]
+    UnbondingNotComplete,
+    
+    #[msg("Submission decision cannot be challenged")]
+    ChallengeNotAllowed,
+    
+    #[msg("Challenge window has closed")]
+    ChallengeWindowClosed,
+    
+    #[msg("Challenge is not open")]
+    ChallengeNotOpen,
+    
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    
+    #[msg("Review deadline has not passed")]
+    ReviewDeadlineNotReached,
+    
+    #[msg("No deposit held for this submission")]
+    NoDepositHeld,
+    
+    #[msg("Submission outcome is not final")]
+    SubmissionNotFinal,
+    
+    #[msg("Too many submissions awaiting review")]
+    TooManyPendingSubmissions,
+    
+    #[msg("Daily submission limit reached")]
+    DailySubmissionLimitReached,
+    
+    #[msg("Invalid registry config")]
+    InvalidConfig,
+    
+    #[msg("Quest prerequisites not met")]
+    PrerequisitesNotMet,
+    
+    #[msg("Too many quest prerequisites")]
+    TooManyPrerequisites,
+    
+    #[msg("No quest ids left for this location")]
+    QuestIdsExhausted,
+    
+    #[msg("User level too low for this quest")]
+    LevelTooLow,
+    
+    #[msg("User rank tier too low for this quest")]
+    RankTierTooLow,
+    
+    #[msg("Quest has reached its completion limit")]
+    QuestCapacityReached,
+    
+    #[msg("Invalid quest schedule")]
+    InvalidQuestSchedule,
+    
+    #[msg("Quest has not started yet")]
+    QuestNotStarted,
+    
+    #[msg("Quest has ended")]
+    QuestEnded,
+    
+    #[msg("Invalid repeat settings")]
+    InvalidRepeatSettings,
+    
+    #[msg("Repeat epoch does not match the current cooldown window")]
+    InvalidRepeatEpoch,
+    
+    #[msg("Invalid waypoint")]
+    InvalidWaypoint,
+    
+    #[msg("Waypoints must be visited in order")]
+    WaypointOutOfOrder,
+    
+    #[msg("Waypoint already visited")]
+    WaypointAlreadyVisited,
+    
+    #[msg("Trail not completed")]
+    TrailIncomplete,
+    
+    #[msg("Not enough landmarks visited")]
+    NotEnoughLandmarks,
+    
+    #[msg("User already participates in this submission")]
+    AlreadyParticipating,
+    
+    #[msg("Submission already has enough participants")]
+    ParticipantsFull,
+    
+    #[msg("Checkpoint is not awaiting review")]
+    CheckpointNotPending,
+    
+    #[msg("Quiz answers must be submitted through commit-reveal")]
+    QuizRevealRequired,
+    
+    #[msg("Revealed answer does not match the commitment")]
+    CommitmentMismatch,
+    
+    #[msg("Reveal must happen after the commit slot")]
+    RevealTooEarly,
+    
+    #[msg("Invalid party size")]
+    InvalidPartySize,
+    
+    #[msg("Party is full")]
+    PartyFull,
+    
+    #[msg("User is already in the party")]
+    AlreadyInParty,
+    
+    #[msg("User is not a member of the party")]
+    NotPartyMember,
+    
+    #[msg("Missing user profile or completions account for a party member")]
+    PartyMemberProfileMissing,
+    
+    #[msg("Account already uses the current layout")]
+    AlreadyMigrated,
+    
+    #[msg("Username is already taken")]
+    UsernameTaken,
+    
+    #[msg("Submission already exists and has not expired")]
+    SubmissionAlreadyExists,
+    
+    #[msg("Validator already voted on this attempt")]
+    AlreadyVoted,
+    
+    #[msg("Prerequisite must reference another existing quest")]
+    InvalidPrerequisite,
+    
+    #[msg("Quest capacity is below its completion count")]
+    InvalidCapacity,
+    
+    #[msg("Missing user profile or completions account for a participant")]
+    ParticipantAccountsMissing,
+    
+    #[msg("Validators cannot review their own check-ins")]
+    SelfReview,
+    
+    #[msg("Checkpoints of a trail must be approved in order")]
+    CheckpointOutOfOrder,
+    
+    #[msg("Remove all members before disbanding the party")]
+    PartyNotEmpty,
+    
+    #[msg("Stake cannot be withdrawn while a challenge against the validator is open")]
+    ChallengesOpen,
 }
@@ -1,13 +1,14 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::XploraError;
-use crate::utils::{validate_quest, get_current_timestamp};
+use crate::utils::{validate_quest, validate_prerequisites, get_current_timestamp, assign_quest_id};
 
 /// Add a new quest to an existing location
 pub fn add_quest_to_location(
     ctx: Context<crate::AddQuestToLocation>,
     quest: Quest,
 ) -> Result<()> {
+    let location_key = ctx.accounts.location_quests.key();
     let location_quests = &mut ctx.accounts.location_quests;
     
     // Check if location is initialized
@@ -25,6 +26,9 @@ pub fn add_quest_to_location(
     // Set creation timestamp
     let mut quest_with_timestamp = quest;
     quest_with_timestamp.created_at = get_current_timestamp();
+    assign_quest_id(location_quests, &mut quest_with_timestamp)?;
+    validate_prerequisites(&quest_with_timestamp, &location_key, location_quests)?;
+    quest_with_timestamp.completions = 0;
     
     // Add quest to location
     location_quests.quests.push(quest_with_timestamp);
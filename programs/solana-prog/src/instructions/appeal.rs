@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::XploraError;
+use crate::instructions::approve_submission::settle_approval;
+use crate::instructions::party::distribute_shared_rewards;
+
+/// Appeal a rejected submission within the appeal window
+pub fn appeal_submission(
+    ctx: Context<crate::AppealSubmission>,
+    reason: String,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let submission = &mut ctx.accounts.submission;
+    
+    // Only a first rejection can be appealed
+    require!(
+        submission.status == SubmissionStatus::Rejected && submission.appealed_at == 0,
+        XploraError::AppealNotAllowed
+    );
+    
+    let rejected_at = submission.validated_at.ok_or(XploraError::AppealNotAllowed)?;
+    let appeal_deadline = rejected_at
+        .checked_add(ctx.accounts.registry.config.appeal_window_seconds)
+        .ok_or(XploraError::Overflow)?;
+    require!(
+        clock.unix_timestamp <= appeal_deadline,
+        XploraError::AppealWindowClosed
+    );
+    
+    require!(
+        !reason.is_empty() && reason.len() <= QuestSubmission::MAX_REASON_LEN,
+        XploraError::InvalidDescription
+    );
+    
+    submission.status = SubmissionStatus::Appealed;
+    submission.appeal_reason = reason.clone();
+    submission.appealed_at = clock.unix_timestamp;
+    
+    msg!("Appeal filed for submission: {}", submission.key());
+    msg!("Reason: {}", reason);
+    
+    emit!(AppealFiledEvent {
+        user: submission.user,
+        submission_pda: submission.key(),
+        reason,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Resolve an appeal. Overturning approves the submission and pays rewards
+/// retroactively; upholding makes the rejection final.
+pub fn resolve_appeal(
+    ctx: Context<crate::ResolveAppeal>,
+    overturn: bool,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let submission_key = ctx.accounts.submission.key();
+    let location_quests_key = ctx.accounts.location_quests.key();
+    let reviewer = ctx.accounts.reviewer.key();
+    let submission = &mut ctx.accounts.submission;
+    
+    require!(
+        submission.status == SubmissionStatus::Appealed,
+        XploraError::SubmissionNotAppealed
+    );
+    
+    if overturn {
+        let quest = ctx.accounts.location_quests.quest_by_id_mut(submission.quest_id)?;
+        let approved = settle_approval(
+            submission,
+            &ctx.accounts.user.to_account_info(),
+            &mut ctx.accounts.user_profile,
+            &mut ctx.accounts.completions,
+            quest,
+            reviewer,
+            0,
+        )?;
+        if approved {
+            distribute_shared_rewards(
+                submission,
+                quest,
+                &location_quests_key,
+                ctx.remaining_accounts,
+                ctx.program_id,
+                clock.unix_timestamp,
+            )?;
+        }
+    } else {
+        submission.status = SubmissionStatus::Rejected;
+        submission.validator = Some(reviewer);
+        submission.validated_at = Some(clock.unix_timestamp);
+    }
+    
+    msg!("Appeal {} by {}", if overturn { "upheld" } else { "denied" }, reviewer);
+    
+    emit!(AppealResolvedEvent {
+        user: submission.user,
+        submission_pda: submission_key,
+        reviewer,
+        overturned: overturn,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct AppealFiledEvent {
+    pub user: Pubkey,
+    pub submission_pda: Pubkey,
+    pub reason: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AppealResolvedEvent {
+    pub user: Pubkey,
+    pub submission_pda: Pubkey,
+    pub reviewer: Pubkey,
+    pub overturned: bool,
+    pub timestamp: i64,
+}
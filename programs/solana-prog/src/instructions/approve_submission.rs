@@ -1,15 +1,21 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::XploraError;
+use crate::utils::transfer_lamports_from_pda;
+use crate::instructions::reward_vault::pay_review_fee;
+use crate::instructions::reject_submission::settle_rejection;
+use crate::instructions::party::distribute_shared_rewards;
 
 /// Approve a quest submission and distribute rewards
 pub fn approve_submission(
     ctx: Context<crate::ApproveSubmission>,
 ) -> Result<()> {
     let clock = Clock::get()?;
+    let submission_key = ctx.accounts.submission.key();
+    let location_quests_key = ctx.accounts.location_quests.key();
     let submission = &mut ctx.accounts.submission;
     let profile = &mut ctx.accounts.user_profile;
-    let location_quests = &ctx.accounts.location_quests;
+    let location_quests = &mut ctx.accounts.location_quests;
     
     // Verify submission is pending
     require!(
@@ -18,47 +24,156 @@ pub fn approve_submission(
     );
     
     // Get quest details for reward calculation
-    let quest = &location_quests.quests[submission.quest_index as usize];
+    let quest = location_quests.quest_by_id_mut(submission.quest_id)?;
+    
+    // Consensus quests are resolved by validator votes
+    require!(quest.required_votes <= 1, XploraError::ConsensusRequired);
+    
+    profile.release_pending_submission();
+    let approved = settle_approval(
+        submission,
+        &ctx.accounts.user.to_account_info(),
+        profile,
+        &mut ctx.accounts.completions,
+        quest,
+        ctx.accounts.validator.key(),
+        0,
+    )?;
+    if approved {
+        distribute_shared_rewards(
+            submission,
+            quest,
+            &location_quests_key,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            clock.unix_timestamp,
+        )?;
+        
+        // Sold-out rejections are automatic, so only real approvals earn the fee
+        pay_review_fee(
+            &mut ctx.accounts.reward_vault,
+            &mut ctx.accounts.validator_profile,
+            &ctx.accounts.validator.to_account_info(),
+            &ctx.accounts.registry.config,
+            submission_key,
+            clock.unix_timestamp,
+        )?;
+    }
+    
+    Ok(())
+}
+
+/// Mark a submission approved, credit XP and tokens to the user profile,
+/// mark the quest completed, refund the deposit to `user` and emit the reward
+/// event. Shared by every approval path. `validator_share_bps` of the token
+/// reward is set aside for consensus validators.
+/// Whether this is a repeat is decided here, against the completion bitmap at
+/// approval time. Repeats earn `repeat_xp_bps` of the XP and tokens and do not
+/// use up capacity or early-bird slots.
+/// Returns false if the quest filled up while the submission was pending, or a
+/// social quest lacks participants, in which case the submission is rejected
+/// instead.
+pub(crate) fn settle_approval<'info>(
+    submission: &mut Account<'info, QuestSubmission>,
+    user: &AccountInfo<'info>,
+    profile: &mut UserProfile,
+    completions: &mut QuestCompletions,
+    quest: &mut Quest,
+    validator: Pubkey,
+    validator_share_bps: u64,
+) -> Result<bool> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let submission_key = submission.key();
+    let is_repeat = completions.is_completed(quest.id);
+    submission.is_repeat = is_repeat;
+    
+    // Social quests without enough co-submitters are rejected
+    if let QuestType::Social { required_participants } = quest.quest_type {
+        if submission.participants.len() + 1 < required_participants as usize {
+            settle_rejection(
+                submission,
+                submission_key,
+                validator,
+                String::from("Not enough participants"),
+                timestamp,
+            )?;
+            return Ok(false);
+        }
+    }
+    
+    // Capacity is claimed at first approval; later approvals of a full quest are rejected
+    if !is_repeat && quest.is_full() {
+        settle_rejection(
+            submission,
+            submission_key,
+            validator,
+            String::from("Quest completion limit reached"),
+            timestamp,
+        )?;
+        return Ok(false);
+    }
+    let early_bird = !is_repeat && quest.completions < quest.early_bird_slots;
+    if !is_repeat {
+        quest.completions = quest.completions.checked_add(1)
+            .ok_or(XploraError::Overflow)?;
+    }
     
     // Calculate XP reward based on difficulty
-    let base_xp = constants::BASE_XP_REWARD;
-    let difficulty_multiplier = match quest.difficulty {
-        Difficulty::Easy => 1.0,
-        Difficulty::Medium => constants::DIFFICULTY_MULTIPLIER_MEDIUM,
-        Difficulty::Hard => constants::DIFFICULTY_MULTIPLIER_HARD,
-    };
-    let xp_reward = (base_xp as f64 * difficulty_multiplier) as u64;
+    let xp_reward = quest_xp_reward(quest, is_repeat)?;
     
     // Calculate token reward based on difficulty and rank tier
-    let base_tokens = constants::BASE_TOKEN_REWARD;
-    let tier_multiplier = profile.rank_tier.token_multiplier();
-    let token_reward = (base_tokens as f64 * difficulty_multiplier * tier_multiplier) as u64;
+    let mut total_token_reward = quest_token_reward(quest, profile);
+    
+    // Repeat completions earn reduced tokens, at the same rate as XP
+    if is_repeat {
+        total_token_reward = repeat_reward(quest, total_token_reward)?;
+    }
+    
+    // Early-bird bonus for the first approvals
+    if early_bird {
+        let bonus = total_token_reward
+            .checked_mul(quest.early_bird_bonus_bps as u64)
+            .ok_or(XploraError::Overflow)?
+            / constants::BPS_DENOMINATOR;
+        total_token_reward = total_token_reward.checked_add(bonus)
+            .ok_or(XploraError::Overflow)?;
+        msg!("Early-bird bonus: {} tokens", bonus);
+    }
+    
+    // Split off the validators' share
+    let validator_pool = total_token_reward
+        .checked_mul(validator_share_bps)
+        .ok_or(XploraError::Overflow)?
+        / constants::BPS_DENOMINATOR;
+    let token_reward = total_token_reward - validator_pool;
     
     // Update submission
     submission.status = SubmissionStatus::Approved;
-    submission.validator = Some(ctx.accounts.validator.key());
-    submission.validated_at = Some(clock.unix_timestamp);
+    submission.validator = Some(validator);
+    submission.validated_at = Some(timestamp);
     submission.reward_amount = token_reward;
+    submission.xp_reward = xp_reward;
+    submission.validator_reward_pool = validator_pool;
+    completions.mark_completed(quest.id);
+    
+    // Approved submissions get their anti-spam deposit back
+    let deposit = submission.deposit_lamports;
+    if deposit > 0 {
+        submission.deposit_lamports = 0;
+        transfer_lamports_from_pda(&submission.to_account_info(), user, deposit)?;
+        msg!("Deposit of {} lamports refunded", deposit);
+    }
     
     // Update user profile
     profile.quests_completed = profile.quests_completed.checked_add(1)
         .ok_or(XploraError::Overflow)?;
-    profile.experience_points = profile.experience_points.checked_add(xp_reward)
-        .ok_or(XploraError::Overflow)?;
     profile.total_tokens_earned = profile.total_tokens_earned.checked_add(token_reward)
         .ok_or(XploraError::Overflow)?;
-    profile.last_active = clock.unix_timestamp;
-    
-    // Update level and rank tier
-    let new_level = profile.calculate_level();
-    if new_level > profile.level {
-        profile.level = new_level;
-        profile.rank_tier = RankTier::from_level(new_level);
-        msg!("User leveled up to level {}!", new_level);
-    }
+    profile.last_active = timestamp;
+    award_experience(profile, xp_reward)?;
     
     // Update streak
-    update_streak(profile, clock.unix_timestamp)?;
+    update_streak(profile, timestamp)?;
     
     // Track unique location visited (simplified - just increment)
     // TODO: In production, check if this location is new for the user
@@ -74,20 +189,79 @@ pub fn approve_submission(
     // Emit reward event
     emit!(QuestRewardEvent {
         user: submission.user,
-        submission_pda: submission.key(),
+        submission_pda: submission_key,
         location: submission.location.clone(),
         quest_index: submission.quest_index,
         xp_reward,
         token_reward,
         new_level: profile.level,
-        timestamp: clock.unix_timestamp,
+        timestamp,
     });
     
+    Ok(true)
+}
+
+/// Reward multiplier for a quest difficulty
+fn difficulty_multiplier(difficulty: &Difficulty) -> f64 {
+    match difficulty {
+        Difficulty::Easy => 1.0,
+        Difficulty::Medium => constants::DIFFICULTY_MULTIPLIER_MEDIUM,
+        Difficulty::Hard => constants::DIFFICULTY_MULTIPLIER_HARD,
+    }
+}
+
+/// XP for completing a quest, reduced to `repeat_xp_bps` for a repeat.
+/// First completions of a quest with prerequisites earn the chain bonus.
+pub(crate) fn quest_xp_reward(quest: &Quest, is_repeat: bool) -> Result<u64> {
+    let base_xp = constants::BASE_XP_REWARD;
+    let xp_reward = (base_xp as f64 * difficulty_multiplier(&quest.difficulty)) as u64;
+    
+    if is_repeat {
+        return repeat_reward(quest, xp_reward);
+    }
+    
+    // Quests with prerequisites conclude a chain
+    if !quest.prerequisites.is_empty() && quest.chain_bonus_xp > 0 {
+        msg!("Quest chain completed! Bonus: {} XP", quest.chain_bonus_xp);
+        return Ok(xp_reward.checked_add(quest.chain_bonus_xp as u64)
+            .ok_or(XploraError::Overflow)?);
+    }
+    
+    Ok(xp_reward)
+}
+
+/// Share of a reward earned by a repeat completion
+pub(crate) fn repeat_reward(quest: &Quest, amount: u64) -> Result<u64> {
+    Ok(amount
+        .checked_mul(quest.repeat_xp_bps as u64)
+        .ok_or(XploraError::Overflow)?
+        / constants::BPS_DENOMINATOR)
+}
+
+/// Token reward for completing a quest at the profile's rank tier
+pub(crate) fn quest_token_reward(quest: &Quest, profile: &UserProfile) -> u64 {
+    let base_tokens = constants::BASE_TOKEN_REWARD;
+    let tier_multiplier = profile.rank_tier.token_multiplier();
+    (base_tokens as f64 * difficulty_multiplier(&quest.difficulty) * tier_multiplier) as u64
+}
+
+/// Credit XP to a profile and update its level and rank tier
+pub(crate) fn award_experience(profile: &mut UserProfile, xp: u64) -> Result<()> {
+    profile.experience_points = profile.experience_points.checked_add(xp)
+        .ok_or(XploraError::Overflow)?;
+    
+    let new_level = profile.calculate_level();
+    if new_level > profile.level {
+        profile.level = new_level;
+        profile.rank_tier = RankTier::from_level(new_level);
+        msg!("User leveled up to level {}!", new_level);
+    }
+    
     Ok(())
 }
 
 /// Helper function to update user streak
-fn update_streak(profile: &mut UserProfile, current_timestamp: i64) -> Result<()> {
+pub(crate) fn update_streak(profile: &mut UserProfile, current_timestamp: i64) -> Result<()> {
     const ONE_DAY_SECONDS: i64 = 86_400;
     
     let days_since_last = (current_timestamp - profile.last_quest_date) / ONE_DAY_SECONDS;
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::XploraError;
+use crate::utils::load_ed25519_attestation;
+use crate::instructions::approve_submission::settle_approval;
+use crate::instructions::party::distribute_shared_rewards;
+
+/// Approve a submission with a verdict signed by the registered AI validator.
+/// Any relayer may submit the transaction; the verdict signature is checked
+/// via the preceding Ed25519 sigverify instruction.
+pub fn approve_with_attestation(
+    ctx: Context<crate::ApproveWithAttestation>,
+    confidence_bps: u16,
+    model_version: String,
+    signed_at: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &ctx.accounts.registry.config;
+    let submission_key = ctx.accounts.submission.key();
+    let location_quests_key = ctx.accounts.location_quests.key();
+    let submission = &mut ctx.accounts.submission;
+    
+    // Verify submission is pending
+    require!(
+        submission.status == SubmissionStatus::Pending,
+        XploraError::SubmissionNotPending
+    );
+    
+    require!(
+        !model_version.is_empty() && model_version.len() <= AiVerdict::MAX_MODEL_VERSION_LEN,
+        XploraError::InvalidModelVersion
+    );
+    require!(
+        confidence_bps as u64 <= constants::BPS_DENOMINATOR
+            && confidence_bps >= config.min_ai_confidence_bps,
+        XploraError::ConfidenceTooLow
+    );
+    
+    // Verdict must be signed after the submission was made
+    require!(
+        signed_at >= submission.submitted_at
+            && signed_at <= clock.unix_timestamp + constants::MAX_CLOCK_SKEW_SECONDS,
+        XploraError::InvalidAttestation
+    );
+    
+    let verdict = AiVerdict {
+        signer: config.ai_validator,
+        confidence_bps,
+        model_version,
+        signed_at,
+    };
+    
+    // The preceding instruction must carry the AI validator's signature over this verdict
+    let attestation = load_ed25519_attestation(&ctx.accounts.instructions_sysvar)?
+        .ok_or(XploraError::InvalidAttestation)?;
+    require!(
+        config.ai_validator != Pubkey::default()
+            && attestation.signer == config.ai_validator
+            && attestation.message == verdict.attestation_message(&submission_key),
+        XploraError::InvalidAttestation
+    );
+    
+    msg!("AI verdict accepted: {} bps, model {}", confidence_bps, verdict.model_version);
+    
+    submission.ai_verdict = Some(verdict);
+    
+    // Consensus quests are resolved by validator votes
+    let quest = ctx.accounts.location_quests.quest_by_id_mut(submission.quest_id)?;
+    require!(quest.required_votes <= 1, XploraError::ConsensusRequired);
+    
+    ctx.accounts.user_profile.release_pending_submission();
+    let approved = settle_approval(
+        submission,
+        &ctx.accounts.user.to_account_info(),
+        &mut ctx.accounts.user_profile,
+        &mut ctx.accounts.completions,
+        quest,
+        config.ai_validator,
+        0,
+    )?;
+    if approved {
+        distribute_shared_rewards(
+            submission,
+            quest,
+            &location_quests_key,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            clock.unix_timestamp,
+        )?;
+    }
+    
+    Ok(())
+}
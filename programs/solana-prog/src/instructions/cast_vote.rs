@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::XploraError;
+use crate::instructions::approve_submission::{quest_token_reward, settle_approval};
+use crate::instructions::party::distribute_shared_rewards;
+use crate::instructions::reject_submission::settle_rejection;
+
+/// Cast a validator vote on a consensus submission, resolving it once
+/// either side reaches the quest's required vote count. `fraud` is ignored
+/// on approve votes.
+pub fn cast_vote(ctx: Context<crate::CastVote>, approve: bool, fraud: bool) -> Result<()> {
+    let clock = Clock::get()?;
+    let submission_key = ctx.accounts.submission.key();
+    let location_quests_key = ctx.accounts.location_quests.key();
+    let validator_key = ctx.accounts.validator.key();
+    let submission = &mut ctx.accounts.submission;
+    
+    // Verify submission is pending
+    require!(
+        submission.status == SubmissionStatus::Pending,
+        XploraError::SubmissionNotPending
+    );
+    
+    let quest = ctx.accounts.location_quests.quest_by_id_mut(submission.quest_id)?;
+    require!(quest.required_votes > 1, XploraError::ConsensusNotEnabled);
+    
+    // A vote left over from an expired attempt may be recast
+    let vote = &mut ctx.accounts.vote;
+    require!(
+        vote.validator == Pubkey::default() || vote.attempt_number != submission.attempt_number,
+        XploraError::AlreadyVoted
+    );
+    
+    // Record the vote
+    vote.submission = submission_key;
+    vote.validator = validator_key;
+    vote.approve = approve;
+    vote.fraud = fraud && !approve;
+    vote.voted_at = clock.unix_timestamp;
+    vote.reward_claimed = false;
+    vote.bump = ctx.bumps.vote;
+    vote.attempt_number = submission.attempt_number;
+    
+    let validator_profile = &mut ctx.accounts.validator_profile;
+    validator_profile.votes_cast = validator_profile.votes_cast.checked_add(1)
+        .ok_or(XploraError::Overflow)?;
+    
+    if approve {
+        submission.approve_votes = submission.approve_votes.checked_add(1)
+            .ok_or(XploraError::Overflow)?;
+    } else {
+        submission.reject_votes = submission.reject_votes.checked_add(1)
+            .ok_or(XploraError::Overflow)?;
+        if vote.fraud {
+            submission.fraud_votes = submission.fraud_votes.checked_add(1)
+                .ok_or(XploraError::Overflow)?;
+        }
+    }
+    
+    msg!("Vote cast by {}: {}", validator_key, if approve { "approve" } else { "reject" });
+    msg!("Votes: {} approve, {} reject (need {})",
+        submission.approve_votes, submission.reject_votes, quest.required_votes);
+    
+    emit!(VoteCastEvent {
+        submission_pda: submission_key,
+        validator: validator_key,
+        approve,
+        fraud: vote.fraud,
+        approve_votes: submission.approve_votes,
+        reject_votes: submission.reject_votes,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    // Resolve once quorum is reached
+    if submission.approve_votes >= quest.required_votes {
+        ctx.accounts.user_profile.release_pending_submission();
+        let approved = settle_approval(
+            submission,
+            &ctx.accounts.user.to_account_info(),
+            &mut ctx.accounts.user_profile,
+            &mut ctx.accounts.completions,
+            quest,
+            validator_key,
+            constants::VALIDATOR_REWARD_SHARE_BPS,
+        )?;
+        if approved {
+            distribute_shared_rewards(
+                submission,
+                quest,
+                &location_quests_key,
+                ctx.remaining_accounts,
+                ctx.program_id,
+                clock.unix_timestamp,
+            )?;
+        }
+    } else if submission.reject_votes >= quest.required_votes {
+        ctx.accounts.user_profile.release_pending_submission();
+        
+        // The deposit is forfeited only if most rejecting validators flagged fraud;
+        // rejecting validators share the same pool they would have split on approval
+        submission.flagged_fraud =
+            submission.fraud_votes as u16 * 2 > submission.reject_votes as u16;
+        submission.validator_reward_pool = quest_token_reward(quest, &ctx.accounts.user_profile)
+            .checked_mul(constants::VALIDATOR_REWARD_SHARE_BPS)
+            .ok_or(XploraError::Overflow)?
+            / constants::BPS_DENOMINATOR;
+        settle_rejection(
+            submission,
+            submission_key,
+            validator_key,
+            String::from("Rejected by validator consensus"),
+            clock.unix_timestamp,
+        )?;
+    }
+    
+    Ok(())
+}
+
+/// Claim a validator's share of the reward pool for a vote that agreed
+/// with the consensus outcome, on either side
+pub fn claim_vote_reward(ctx: Context<crate::ClaimVoteReward>) -> Result<()> {
+    let submission = &ctx.accounts.submission;
+    let vote = &mut ctx.accounts.vote;
+    
+    let agreeing_votes = match submission.status {
+        _ if vote.attempt_number != submission.attempt_number => 0,
+        SubmissionStatus::Approved if vote.approve => submission.approve_votes,
+        SubmissionStatus::Rejected if !vote.approve => submission.reject_votes,
+        _ => 0,
+    };
+    require!(
+        agreeing_votes > 0 && !vote.reward_claimed,
+        XploraError::NoVoteReward
+    );
+    
+    let share = submission.validator_reward_pool / agreeing_votes as u64;
+    vote.reward_claimed = true;
+    
+    let validator_profile = &mut ctx.accounts.validator_profile;
+    validator_profile.agreeing_votes = validator_profile.agreeing_votes.checked_add(1)
+        .ok_or(XploraError::Overflow)?;
+    validator_profile.tokens_earned = validator_profile.tokens_earned.checked_add(share)
+        .ok_or(XploraError::Overflow)?;
+    
+    msg!("Validator {} claimed {} tokens", validator_profile.validator, share);
+    
+    Ok(())
+}
+
+#[event]
+pub struct VoteCastEvent {
+    pub submission_pda: Pubkey,
+    pub validator: Pubkey,
+    pub approve: bool,
+    pub fraud: bool,
+    pub approve_votes: u8,
+    pub reject_votes: u8,
+    pub timestamp: i64,
+}
@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::errors::XploraError;
+use crate::utils::transfer_lamports_from_pda;
+
+/// Challenge a validator's approval or rejection of a submission,
+/// bonding `CHALLENGE_BOND_LAMPORTS` into the challenge account
+pub fn challenge_review(
+    ctx: Context<crate::ChallengeReview>,
+    reason: String,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let submission = &ctx.accounts.submission;
+    
+    require!(
+        submission.status == SubmissionStatus::Approved
+            || submission.status == SubmissionStatus::Rejected,
+        XploraError::ChallengeNotAllowed
+    );
+    let validator = submission.validator.ok_or(XploraError::ChallengeNotAllowed)?;
+    let validated_at = submission.validated_at.ok_or(XploraError::ChallengeNotAllowed)?;
+    let window_closes_at = validated_at.checked_add(constants::CHALLENGE_WINDOW_SECONDS)
+        .ok_or(XploraError::Overflow)?;
+    require!(
+        clock.unix_timestamp <= window_closes_at,
+        XploraError::ChallengeWindowClosed
+    );
+    
+    require!(
+        !reason.is_empty() && reason.len() <= QuestSubmission::MAX_REASON_LEN,
+        XploraError::InvalidDescription
+    );
+    
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.challenger.to_account_info(),
+                to: ctx.accounts.challenge.to_account_info(),
+            },
+        ),
+        constants::CHALLENGE_BOND_LAMPORTS,
+    )?;
+    
+    let challenge = &mut ctx.accounts.challenge;
+    challenge.submission = submission.key();
+    challenge.validator = validator;
+    challenge.challenger = ctx.accounts.challenger.key();
+    challenge.reason = reason.clone();
+    challenge.status = ChallengeStatus::Open;
+    challenge.slashed_lamports = 0;
+    challenge.created_at = clock.unix_timestamp;
+    challenge.resolved_at = None;
+    challenge.bump = ctx.bumps.challenge;
+    challenge.bond_lamports = constants::CHALLENGE_BOND_LAMPORTS;
+    
+    // Keeps the validator's stake from being withdrawn until this is resolved
+    let profile = &mut ctx.accounts.validator_profile;
+    profile.open_challenges = profile.open_challenges.checked_add(1)
+        .ok_or(XploraError::Overflow)?;
+    
+    msg!("Review by {} challenged", validator);
+    msg!("Reason: {}", reason);
+    
+    emit!(ReviewChallengedEvent {
+        submission_pda: challenge.submission,
+        validator,
+        challenger: challenge.challenger,
+        reason,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Uphold a challenge and slash the validator's stake into the treasury.
+/// Bonded stake is slashed first, then stake still unbonding. The
+/// challenger's bond is returned.
+pub fn slash_validator(ctx: Context<crate::SlashValidator>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let profile = &mut ctx.accounts.validator_profile;
+    
+    require!(
+        amount > 0 && amount <= profile.staked_lamports + profile.unbonding_lamports,
+        XploraError::InsufficientStake
+    );
+    
+    let from_staked = amount.min(profile.staked_lamports);
+    profile.staked_lamports -= from_staked;
+    profile.unbonding_lamports -= amount - from_staked;
+    profile.slashed_lamports = profile.slashed_lamports.checked_add(amount)
+        .ok_or(XploraError::Overflow)?;
+    profile.open_challenges = profile.open_challenges.saturating_sub(1);
+    
+    transfer_lamports_from_pda(
+        &profile.to_account_info(),
+        &ctx.accounts.treasury.to_account_info(),
+        amount,
+    )?;
+    
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_collected = treasury.total_collected.checked_add(amount)
+        .ok_or(XploraError::Overflow)?;
+    
+    let challenge = &mut ctx.accounts.challenge;
+    transfer_lamports_from_pda(
+        &challenge.to_account_info(),
+        &ctx.accounts.challenger.to_account_info(),
+        challenge.bond_lamports,
+    )?;
+    challenge.status = ChallengeStatus::Upheld;
+    challenge.slashed_lamports = amount;
+    challenge.resolved_at = Some(clock.unix_timestamp);
+    
+    msg!("Validator {} slashed {} lamports", profile.validator, amount);
+    
+    emit!(ValidatorSlashedEvent {
+        validator: profile.validator,
+        submission_pda: challenge.submission,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Dismiss a challenge without slashing, forfeiting the challenger's bond
+/// to the treasury
+pub fn dismiss_challenge(ctx: Context<crate::DismissChallenge>) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    let bond = challenge.bond_lamports;
+    transfer_lamports_from_pda(
+        &challenge.to_account_info(),
+        &ctx.accounts.treasury.to_account_info(),
+        bond,
+    )?;
+    
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_collected = treasury.total_collected.checked_add(bond)
+        .ok_or(XploraError::Overflow)?;
+    
+    challenge.status = ChallengeStatus::Dismissed;
+    challenge.resolved_at = Some(Clock::get()?.unix_timestamp);
+    
+    let profile = &mut ctx.accounts.validator_profile;
+    profile.open_challenges = profile.open_challenges.saturating_sub(1);
+    
+    msg!("Challenge against {} dismissed", challenge.validator);
+    
+    Ok(())
+}
+
+#[event]
+pub struct ReviewChallengedEvent {
+    pub submission_pda: Pubkey,
+    pub validator: Pubkey,
+    pub challenger: Pubkey,
+    pub reason: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ValidatorSlashedEvent {
+    pub validator: Pubkey,
+    pub submission_pda: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::XploraError;
+use crate::utils::validate_username;
+
+/// Change username, releasing the old handle and claiming the new one
+pub fn change_username(
+    ctx: Context<crate::ChangeUsername>,
+    username: String,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    // A rename that only changes casing keeps the same handle record
+    let same_handle = ctx.accounts.old_username_record.as_ref()
+        .is_some_and(|old| old.key() == ctx.accounts.new_username_record.key());
+    require!(
+        same_handle || ctx.accounts.new_username_record.owner == Pubkey::default(),
+        XploraError::UsernameTaken
+    );
+    
+    // Release the old handle
+    if let Some(old_username_record) = &ctx.accounts.old_username_record {
+        if !same_handle {
+            old_username_record.close(ctx.accounts.user.to_account_info())?;
+        }
+    }
+    
+    let profile = &mut ctx.accounts.user_profile;
+    
+    // Validate username
+    validate_username(&username)?;
+    
+    // Rate limit handle changes
+    let next_change_at = profile.username_changed_at
+        .checked_add(constants::USERNAME_CHANGE_COOLDOWN_SECONDS)
+        .ok_or(XploraError::Overflow)?;
+    require!(
+        clock.unix_timestamp >= next_change_at,
+        XploraError::UsernameChangeTooSoon
+    );
+    
+    let has_handle = !profile.username.is_empty();
+    require!(
+        ctx.accounts.old_username_record.is_some() == has_handle,
+        XploraError::UsernameRecordMismatch
+    );
+    
+    let old_username = std::mem::replace(&mut profile.username, username.clone());
+    profile.username_changed_at = clock.unix_timestamp;
+    profile.last_active = clock.unix_timestamp;
+    
+    // Claim the new handle (or re-case the existing one)
+    let username_record = &mut ctx.accounts.new_username_record;
+    username_record.owner = profile.user;
+    username_record.username = username.clone();
+    username_record.claimed_at = clock.unix_timestamp;
+    username_record.bump = ctx.bumps.new_username_record;
+    
+    msg!("Username changed from {} to {}", old_username, username);
+    
+    emit!(UsernameChangedEvent {
+        user: profile.user,
+        old_username,
+        new_username: username,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct UsernameChangedEvent {
+    pub user: Pubkey,
+    pub old_username: String,
+    pub new_username: String,
+    pub timestamp: i64,
+}
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::XploraError;
-use crate::utils::{validate_location, validate_quest, get_current_timestamp, derive_location_quests_pda};
+use crate::utils::{
+    validate_location, validate_quest, validate_prerequisites, get_current_timestamp,
+    derive_location_quests_pda, assign_quest_id,
+};
 
 /// Create quests for a new location
 pub fn create_location_quests(
@@ -34,7 +37,15 @@ pub fn create_location_quests(
     // Initialize location quests account
     let location_quests = &mut ctx.accounts.location_quests;
     location_quests.location = location.clone();
-    location_quests.quests = quests;
+    location_quests.next_quest_id = 0;
+    location_quests.quests = Vec::with_capacity(quests.len());
+    // Prerequisites may reference quests created earlier in the same batch
+    for mut quest in quests {
+        assign_quest_id(location_quests, &mut quest)?;
+        validate_prerequisites(&quest, &expected_pda, location_quests)?;
+        quest.completions = 0;
+        location_quests.quests.push(quest);
+    }
     location_quests.initialized = true;
     location_quests.created_at = current_time;
     location_quests.updated_at = current_time;
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::XploraError;
+use crate::utils::transfer_lamports_from_pda;
+
+/// Settle a rejected submission's anti-spam deposit (approvals refund it
+/// immediately). Honest rejections get it back; rejections flagged as fraud
+/// forfeit it to the treasury. Rejections settle only once they
+/// can no longer be appealed.
+pub fn settle_deposit(ctx: Context<crate::SettleDeposit>) -> Result<()> {
+    let clock = Clock::get()?;
+    let submission_key = ctx.accounts.submission.key();
+    let submission = &mut ctx.accounts.submission;
+    
+    let amount = submission.deposit_lamports;
+    require!(amount > 0, XploraError::NoDepositHeld);
+    
+    require!(
+        submission.status == SubmissionStatus::Rejected,
+        XploraError::SubmissionNotFinal
+    );
+    let rejected_at = submission.validated_at.ok_or(XploraError::SubmissionNotFinal)?;
+    let appeal_deadline = rejected_at
+        .checked_add(ctx.accounts.registry.config.appeal_window_seconds)
+        .ok_or(XploraError::Overflow)?;
+    require!(
+        submission.appealed_at != 0 || clock.unix_timestamp > appeal_deadline,
+        XploraError::SubmissionNotFinal
+    );
+    let forfeited = submission.flagged_fraud;
+    
+    submission.deposit_lamports = 0;
+    
+    if forfeited {
+        transfer_lamports_from_pda(
+            &submission.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            amount,
+        )?;
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_collected = treasury.total_collected.checked_add(amount)
+            .ok_or(XploraError::Overflow)?;
+        msg!("Deposit of {} lamports forfeited", amount);
+    } else {
+        transfer_lamports_from_pda(
+            &submission.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            amount,
+        )?;
+        msg!("Deposit of {} lamports refunded", amount);
+    }
+    
+    emit!(DepositSettledEvent {
+        user: submission.user,
+        submission_pda: submission_key,
+        amount,
+        forfeited,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct DepositSettledEvent {
+    pub user: Pubkey,
+    pub submission_pda: Pubkey,
+    pub amount: u64,
+    pub forfeited: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::XploraError;
+use crate::utils::transfer_lamports_from_pda;
+
+/// Expire a submission left pending past the review SLA.
+/// Permissionless: the account stays as `Expired` so the user can resubmit
+/// at the same address, and any deposit is refunded.
+pub fn expire_submission(ctx: Context<crate::ExpireSubmission>) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &ctx.accounts.registry.config;
+    let submission_key = ctx.accounts.submission.key();
+    let submission = &mut ctx.accounts.submission;
+    
+    require!(
+        submission.status == SubmissionStatus::Pending,
+        XploraError::SubmissionNotPending
+    );
+    let deadline = submission.submitted_at.checked_add(config.review_sla_seconds)
+        .ok_or(XploraError::Overflow)?;
+    require!(
+        config.review_sla_seconds > 0 && clock.unix_timestamp >= deadline,
+        XploraError::ReviewDeadlineNotReached
+    );
+    
+    submission.status = SubmissionStatus::Expired;
+    
+    let deposit = submission.deposit_lamports;
+    if deposit > 0 {
+        submission.deposit_lamports = 0;
+        transfer_lamports_from_pda(
+            &submission.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            deposit,
+        )?;
+    }
+    
+    let profile = &mut ctx.accounts.user_profile;
+    profile.release_pending_submission();
+    
+    // Give the attempt back so the user is not penalised for an unreviewed submission
+    let attempt_restored = config.restore_attempt_on_expiry;
+    if attempt_restored {
+        profile.quests_attempted = profile.quests_attempted.saturating_sub(1);
+    }
+    
+    msg!("Submission expired: {}", submission_key);
+    msg!("Deposit of {} lamports refunded to: {}", deposit, submission.user);
+    
+    emit!(SubmissionExpiredEvent {
+        user: submission.user,
+        submission_pda: submission_key,
+        location: submission.location.clone(),
+        quest_index: submission.quest_index,
+        attempt_restored,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct SubmissionExpiredEvent {
+    pub user: Pubkey,
+    pub submission_pda: Pubkey,
+    pub location: String,
+    pub quest_index: u8,
+    pub attempt_restored: bool,
+    pub timestamp: i64,
+}
@@ -8,6 +8,7 @@ pub fn initialize(ctx: Context<crate::Initialize>, authority: Pubkey) -> Result<
     // Set initial values
     registry.authority = authority;
     registry.total_locations = 0;
+    registry.config = RegistryConfig::default();
     registry.version = constants::PROGRAM_VERSION;
     registry.reserved = [0; 7];
     
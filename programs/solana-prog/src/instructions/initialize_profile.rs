@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
-use crate::errors::XploraError;
+use crate::utils::{validate_username, check_sponsorship};
 
 /// Initialize a user profile
 pub fn initialize_user_profile(
@@ -11,28 +11,30 @@ pub fn initialize_user_profile(
     let profile = &mut ctx.accounts.user_profile;
     
     // Validate username
-    require!(
-        username.len() <= UserProfile::MAX_USERNAME_LEN && !username.is_empty(),
-        XploraError::InvalidUsername
-    );
+    validate_username(&username)?;
+    
+    // Relayer-paid rent must come from an allowed relayer
+    check_sponsorship(
+        ctx.accounts.sponsor_policy.as_deref(),
+        &ctx.accounts.fee_payer.key(),
+        &ctx.accounts.user.key(),
+    )?;
     
     // Initialize profile
-    profile.user = ctx.accounts.user.key();
-    profile.username = username.clone();
-    profile.created_at = clock.unix_timestamp;
-    profile.last_active = clock.unix_timestamp;
-    profile.quests_completed = 0;
-    profile.quests_attempted = 0;
-    profile.experience_points = 0;
-    profile.level = 0;
-    profile.total_tokens_earned = 0;
-    profile.unique_locations = 0;
-    profile.current_streak = 0;
-    profile.longest_streak = 0;
-    profile.last_quest_date = 0;
-    profile.achievements = 0; // No achievements initially
-    profile.rank_tier = RankTier::Bronze;
-    profile.bump = ctx.bumps.user_profile;
+    init_profile_fields(
+        profile,
+        ctx.accounts.user.key(),
+        username.clone(),
+        ctx.bumps.user_profile,
+        clock.unix_timestamp,
+    );
+    
+    // Claim the handle
+    let username_record = &mut ctx.accounts.username_record;
+    username_record.owner = ctx.accounts.user.key();
+    username_record.username = username.clone();
+    username_record.claimed_at = clock.unix_timestamp;
+    username_record.bump = ctx.bumps.username_record;
     
     msg!("User profile created for: {}", ctx.accounts.user.key());
     msg!("Username: {}", username);
@@ -47,6 +49,39 @@ pub fn initialize_user_profile(
     Ok(())
 }
 
+/// Reset a freshly allocated profile to its starting state
+pub(crate) fn init_profile_fields(
+    profile: &mut UserProfile,
+    user: Pubkey,
+    username: String,
+    bump: u8,
+    timestamp: i64,
+) {
+    profile.user = user;
+    profile.username = username;
+    profile.avatar_cid = String::new();
+    profile.bio = String::new();
+    profile.username_changed_at = 0;
+    profile.created_at = timestamp;
+    profile.last_active = timestamp;
+    profile.quests_completed = 0;
+    profile.quests_attempted = 0;
+    profile.experience_points = 0;
+    profile.level = 0;
+    profile.total_tokens_earned = 0;
+    profile.unique_locations = 0;
+    profile.current_streak = 0;
+    profile.longest_streak = 0;
+    profile.last_quest_date = 0;
+    profile.achievements = 0; // No achievements initially
+    profile.sponsored_day = 0;
+    profile.sponsored_today = 0;
+    profile.pending_submissions = 0;
+    profile.recent_submissions = [0; UserProfile::SUBMISSION_HISTORY_LEN];
+    profile.rank_tier = RankTier::Bronze;
+    profile.bump = bump;
+}
+
 #[event]
 pub struct ProfileCreatedEvent {
     pub user: Pubkey,
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::XploraError;
+use crate::utils::{
+    validate_location_proof, verify_location_attestation, check_quest_active,
+    check_quest_eligibility, check_prerequisites,
+};
+use crate::instructions::initialize_profile::{init_profile_fields, ProfileCreatedEvent};
+
+/// Co-submit a pending social quest submission with the participant's own GPS fix.
+/// Participants share the submitter's rewards on approval, so they must meet
+/// the same eligibility and prerequisite requirements.
+pub fn join_submission(
+    ctx: Context<crate::JoinSubmission>,
+    location_proof: LocationProof,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let participant = ctx.accounts.participant.key();
+    let submission_key = ctx.accounts.submission.key();
+    let submission = &mut ctx.accounts.submission;
+    let location_quests = &ctx.accounts.location_quests;
+    
+    require!(
+        submission.status == SubmissionStatus::Pending,
+        XploraError::SubmissionNotPending
+    );
+    
+    let quest = location_quests.quest_by_id(submission.quest_id)?;
+    let QuestType::Social { required_participants } = quest.quest_type else {
+        return err!(XploraError::InvalidQuestType);
+    };
+    check_quest_active(quest, clock.unix_timestamp)?;
+    
+    require!(
+        participant != submission.user && !submission.participants.contains(&participant),
+        XploraError::AlreadyParticipating
+    );
+    require!(
+        submission.participants.len() + 1 < required_participants as usize,
+        XploraError::ParticipantsFull
+    );
+    
+    // Each participant proves their own presence
+    let distance_m = validate_location_proof(
+        &location_proof,
+        quest,
+        &ctx.accounts.registry.config,
+        clock.unix_timestamp,
+    )?;
+    let location_attested = verify_location_attestation(
+        &ctx.accounts.instructions_sysvar,
+        &location_proof,
+        &participant,
+        &location_quests.key(),
+        submission.quest_index,
+        &ctx.accounts.registry.config.location_oracle,
+    )?;
+    require!(
+        location_attested || !quest.requires_location_attestation,
+        XploraError::LocationAttestationRequired
+    );
+    
+    // Create the participant's profile and completion bitmap so rewards can be credited
+    let profile = &mut ctx.accounts.participant_profile;
+    if profile.user == Pubkey::default() {
+        init_profile_fields(
+            profile,
+            participant,
+            String::new(),
+            ctx.bumps.participant_profile,
+            clock.unix_timestamp,
+        );
+        
+        emit!(ProfileCreatedEvent {
+            user: participant,
+            username: String::new(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    let completions = &mut ctx.accounts.participant_completions;
+    if completions.user == Pubkey::default() {
+        completions.user = participant;
+        completions.location_quests = location_quests.key();
+        completions.bump = ctx.bumps.participant_completions;
+    }
+    
+    check_quest_eligibility(quest, profile)?;
+    require!(
+        quest.repeatable || !completions.is_completed(quest.id),
+        XploraError::QuestAlreadyCompleted
+    );
+    check_prerequisites(
+        quest,
+        &participant,
+        &location_quests.key(),
+        completions,
+        ctx.remaining_accounts,
+        ctx.program_id,
+    )?;
+    
+    submission.participants.push(participant);
+    
+    msg!("Participant {} joined ({}m away)", participant, distance_m);
+    msg!("Participants: {}/{}", submission.participants.len() + 1, required_participants);
+    
+    emit!(ParticipantJoinedEvent {
+        submission_pda: submission_key,
+        participant,
+        participants: submission.participants.len() as u8 + 1,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct ParticipantJoinedEvent {
+    pub submission_pda: Pubkey,
+    pub participant: Pubkey,
+    pub participants: u8,
+    pub timestamp: i64,
+}
@@ -0,0 +1,270 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::XploraError;
+use crate::utils::{parse_cid, transfer_lamports_from_pda};
+
+/// Version 1 layout of `LocationQuests`
+#[derive(AnchorDeserialize)]
+struct LegacyLocationQuests {
+    location: String,
+    quests: Vec<LegacyQuest>,
+    initialized: bool,
+    created_at: i64,
+    updated_at: i64,
+    _reserved: [u8; 6],
+}
+
+/// Version 1 layout of `Quest`
+#[derive(AnchorDeserialize)]
+struct LegacyQuest {
+    title: String,
+    description: String,
+    quest_type: QuestType,
+    difficulty: Difficulty,
+    time_to_live_hours: u16,
+    verifiable_landmark: String,
+    landmark_name: String,
+    latitude: f64,
+    longitude: f64,
+    created_at: i64,
+    _reserved: [u8; 4],
+}
+
+/// Version 1 layout of `QuestSubmission`
+#[derive(AnchorDeserialize)]
+struct LegacySubmission {
+    user: Pubkey,
+    location: String,
+    quest_index: u8,
+    ipfs_hash: String,
+    description: String,
+    submitted_at: i64,
+    status: SubmissionStatus,
+    validator: Option<Pubkey>,
+    validated_at: Option<i64>,
+    reward_amount: u64,
+    attempt_number: u8,
+    _bump: u8,
+}
+
+/// Grow the registry to the current layout and fill in the default config
+pub fn migrate_registry(ctx: Context<crate::MigrateRegistry>) -> Result<()> {
+    let info = ctx.accounts.registry.to_account_info();
+    require!(info.data_len() < QuestRegistry::LEN, XploraError::AlreadyMigrated);
+    
+    grow_account(
+        &info,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        QuestRegistry::LEN,
+    )?;
+    
+    let mut data = info.try_borrow_mut_data()?;
+    let mut registry = QuestRegistry::try_deserialize(&mut &data[..])?;
+    require!(
+        registry.authority == ctx.accounts.authority.key(),
+        XploraError::Unauthorized
+    );
+    
+    // Appended fields read back as zeroes
+    registry.config = RegistryConfig::default();
+    registry.version = constants::PROGRAM_VERSION;
+    registry.try_serialize(&mut &mut data[..])?;
+    
+    msg!("Registry migrated to version {}", constants::PROGRAM_VERSION);
+    
+    Ok(())
+}
+
+/// Rewrite a location's quests in the current layout, assigning quest ids
+pub fn migrate_location_quests(
+    ctx: Context<crate::MigrateLocationQuests>,
+    _location: String,
+) -> Result<()> {
+    let info = ctx.accounts.location_quests.to_account_info();
+    require!(
+        info.data_len() < LocationQuests::space(),
+        XploraError::AlreadyMigrated
+    );
+    
+    let legacy = {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == *LocationQuests::DISCRIMINATOR,
+            XploraError::AlreadyMigrated
+        );
+        LegacyLocationQuests::deserialize(&mut &data[8..])?
+    };
+    
+    let quests: Vec<Quest> = legacy.quests.into_iter()
+        .enumerate()
+        .map(|(index, quest)| Quest {
+            title: quest.title,
+            description: quest.description,
+            quest_type: quest.quest_type,
+            difficulty: quest.difficulty,
+            time_to_live_hours: quest.time_to_live_hours,
+            verifiable_landmark: quest.verifiable_landmark,
+            landmark_name: quest.landmark_name,
+            latitude: quest.latitude,
+            longitude: quest.longitude,
+            created_at: quest.created_at,
+            reserved: [0; 4],
+            requires_location_attestation: false,
+            required_votes: 0,
+            id: index as u16,
+            prerequisites: Vec::new(),
+            chain_bonus_xp: 0,
+            min_level: 0,
+            min_rank_tier: None,
+            max_completions: 0,
+            completions: 0,
+            early_bird_slots: 0,
+            early_bird_bonus_bps: 0,
+            starts_at: 0,
+            ends_at: 0,
+            repeatable: false,
+            cooldown_seconds: 0,
+            repeat_xp_bps: 0,
+        })
+        .collect();
+    let location_quests = LocationQuests {
+        location: legacy.location,
+        next_quest_id: quests.len() as u16,
+        quests,
+        initialized: legacy.initialized,
+        created_at: legacy.created_at,
+        updated_at: legacy.updated_at,
+        reserved: [0; 6],
+    };
+    
+    grow_account(
+        &info,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        LocationQuests::space(),
+    )?;
+    location_quests.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    
+    msg!(
+        "Location {} migrated ({} quests)",
+        location_quests.location,
+        location_quests.quests.len()
+    );
+    
+    Ok(())
+}
+
+/// Grow a user profile to the current layout
+pub fn migrate_user_profile(ctx: Context<crate::MigrateUserProfile>) -> Result<()> {
+    let info = ctx.accounts.user_profile.to_account_info();
+    require!(
+        info.data_len() < UserProfile::space(),
+        XploraError::AlreadyMigrated
+    );
+    
+    // Appended fields read back as zeroes: no avatar, bio or rate-limit history
+    grow_account(
+        &info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        UserProfile::space(),
+    )?;
+    UserProfile::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    
+    msg!("Profile migrated for: {}", ctx.accounts.user.key());
+    
+    Ok(())
+}
+
+/// Move a version 1 submission to its current address (repeat epoch 0),
+/// converting its IPFS hash into an evidence attachment. The location must
+/// be migrated first so the quest's stable id can be recorded.
+pub fn migrate_submission(
+    ctx: Context<crate::MigrateSubmission>,
+    _location: String,
+    _quest_index: u8,
+) -> Result<()> {
+    let legacy_info = ctx.accounts.legacy_submission.to_account_info();
+    let legacy = {
+        let data = legacy_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == *QuestSubmission::DISCRIMINATOR,
+            XploraError::AlreadyMigrated
+        );
+        LegacySubmission::deserialize(&mut &data[8..])?
+    };
+    
+    // Version 1 accepted any "Qm..." string, so unparseable hashes are dropped
+    let evidence = match parse_cid(&legacy.ipfs_hash) {
+        Ok(cid) => vec![Evidence {
+            cid,
+            media_type: MediaType::Image,
+            content_hash: [0; 32],
+        }],
+        Err(_) => Vec::new(),
+    };
+    
+    let quest_id = ctx.accounts.location_quests.quests
+        .get(legacy.quest_index as usize)
+        .ok_or(XploraError::InvalidQuestIndex)?
+        .id;
+    
+    // Remaining fields are zero-initialized by `init`
+    let submission = &mut ctx.accounts.submission;
+    submission.user = legacy.user;
+    submission.location = legacy.location;
+    submission.quest_index = legacy.quest_index;
+    submission.quest_id = quest_id;
+    submission.evidence = evidence;
+    submission.description = legacy.description;
+    submission.submitted_at = legacy.submitted_at;
+    submission.status = legacy.status;
+    submission.validator = legacy.validator;
+    submission.validated_at = legacy.validated_at;
+    submission.reward_amount = legacy.reward_amount;
+    submission.attempt_number = legacy.attempt_number;
+    submission.bump = ctx.bumps.submission;
+    
+    // Close the old account, returning its rent to the user who paid it
+    transfer_lamports_from_pda(
+        &legacy_info,
+        &ctx.accounts.user.to_account_info(),
+        legacy_info.lamports(),
+    )?;
+    legacy_info.assign(&system_program::ID);
+    legacy_info.resize(0)?;
+    
+    msg!("Submission migrated for: {}", submission.user);
+    
+    Ok(())
+}
+
+/// Resize a program account, topping its rent up from `payer`
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let shortfall = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    account.resize(new_len)?;
+    
+    Ok(())
+}
@@ -7,3 +7,24 @@ pub mod submit_quest;
 pub mod approve_submission;
 pub mod reject_submission;
 pub mod initialize_profile;
+pub mod update_profile;
+pub mod change_username;
+pub mod sponsor_policy;
+pub mod session_key;
+pub mod update_config;
+pub mod approve_with_attestation;
+pub mod validator;
+pub mod cast_vote;
+pub mod appeal;
+pub mod treasury;
+pub mod staking;
+pub mod challenge;
+pub mod reward_vault;
+pub mod expire_submission;
+pub mod deposit;
+pub mod quest_status;
+pub mod waypoint;
+pub mod join_submission;
+pub mod quiz;
+pub mod party;
+pub mod migrate;
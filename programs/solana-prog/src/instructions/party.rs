@@ -0,0 +1,357 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::XploraError;
+use crate::utils::{check_prerequisites, check_quest_eligibility};
+use crate::instructions::approve_submission::{
+    award_experience, quest_token_reward, quest_xp_reward, repeat_reward, update_streak,
+};
+
+/// Create a party led by the caller
+pub fn create_party(ctx: Context<crate::CreateParty>, max_size: u8) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    require!(
+        max_size >= 2 && max_size as usize <= Party::MAX_MEMBERS,
+        XploraError::InvalidPartySize
+    );
+    
+    let party = &mut ctx.accounts.party;
+    party.leader = ctx.accounts.leader.key();
+    party.members = Vec::new();
+    party.max_size = max_size;
+    party.created_at = clock.unix_timestamp;
+    party.bump = ctx.bumps.party;
+    
+    // The leader counts as a member of its own party
+    let membership = &mut ctx.accounts.leader_membership;
+    membership.member = party.leader;
+    membership.party = party.key();
+    membership.joined_at = clock.unix_timestamp;
+    membership.bump = ctx.bumps.leader_membership;
+    
+    msg!("Party created by {} (max {} members)", party.leader, max_size);
+    
+    emit!(PartyCreatedEvent {
+        party: party.key(),
+        leader: party.leader,
+        max_size,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Add a member to a party; both the leader and the member must sign.
+/// A user belongs to one party at a time, enforced by its membership PDA.
+pub fn add_party_member(ctx: Context<crate::AddPartyMember>) -> Result<()> {
+    let clock = Clock::get()?;
+    let member = ctx.accounts.member.key();
+    let party = &mut ctx.accounts.party;
+    
+    require!(
+        member != party.leader && !party.members.contains(&member),
+        XploraError::AlreadyInParty
+    );
+    require!(!party.is_full(), XploraError::PartyFull);
+    
+    party.members.push(member);
+    
+    let membership = &mut ctx.accounts.member_membership;
+    membership.member = member;
+    membership.party = party.key();
+    membership.joined_at = clock.unix_timestamp;
+    membership.bump = ctx.bumps.member_membership;
+    
+    msg!("{} joined party of {}", member, party.leader);
+    msg!("Party size: {}/{}", party.members.len() + 1, party.max_size);
+    
+    emit!(PartyMembershipEvent {
+        party: party.key(),
+        member,
+        joined: true,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Remove a member from a party; signed by the leader or the member leaving.
+/// The membership PDA is closed back to the member.
+pub fn remove_party_member(ctx: Context<crate::RemovePartyMember>, member: Pubkey) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    let party = &mut ctx.accounts.party;
+    
+    require!(
+        signer == party.leader || signer == member,
+        XploraError::Unauthorized
+    );
+    let position = party.members.iter()
+        .position(|m| *m == member)
+        .ok_or(XploraError::NotPartyMember)?;
+    party.members.remove(position);
+    
+    msg!("{} left party of {}", member, party.leader);
+    
+    emit!(PartyMembershipEvent {
+        party: party.key(),
+        member,
+        joined: false,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Disband an empty party, closing it and the leader's membership to the leader
+pub fn disband_party(ctx: Context<crate::DisbandParty>) -> Result<()> {
+    require!(ctx.accounts.party.members.is_empty(), XploraError::PartyNotEmpty);
+    
+    msg!("Party of {} disbanded", ctx.accounts.party.leader);
+    
+    Ok(())
+}
+
+/// Create the caller's completion bitmap for a location, so a party can
+/// submit there on the caller's behalf
+pub fn initialize_completions(ctx: Context<crate::InitializeCompletions>, _location: String) -> Result<()> {
+    let completions = &mut ctx.accounts.completions;
+    completions.user = ctx.accounts.user.key();
+    completions.location_quests = ctx.accounts.location_quests.key();
+    completions.bump = ctx.bumps.completions;
+    
+    msg!("Completions created for {} at {}", completions.user, completions.location_quests);
+    
+    Ok(())
+}
+
+/// Verify every party member may take on a quest: each must meet its level and
+/// rank requirements and have completed its prerequisites. Each member's
+/// `UserProfile` and `QuestCompletions` PDAs for the location must be passed in
+/// `remaining_accounts`, along with completions for other-location prerequisites.
+pub(crate) fn check_party_members(
+    members: &[Pubkey],
+    quest: &Quest,
+    location_quests: &Pubkey,
+    remaining_accounts: &[AccountInfo],
+    program_id: &Pubkey,
+) -> Result<()> {
+    for member in members {
+        let info = find_member_account(
+            remaining_accounts,
+            &[b"user_profile", member.as_ref()],
+            program_id,
+            XploraError::PartyMemberProfileMissing,
+        )?;
+        let profile = UserProfile::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        check_quest_eligibility(quest, &profile)?;
+        
+        let info = find_member_account(
+            remaining_accounts,
+            &[b"completions", member.as_ref(), location_quests.as_ref()],
+            program_id,
+            XploraError::PartyMemberProfileMissing,
+        )?;
+        let completions = QuestCompletions::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        check_prerequisites(
+            quest,
+            member,
+            location_quests,
+            &completions,
+            remaining_accounts,
+            program_id,
+        )?;
+    }
+    
+    Ok(())
+}
+
+/// Credit an approved quest's XP and tokens to every party member and social
+/// quest participant, and mark the quest completed for each. Rewards follow
+/// each member's own completions and rank tier: members who already completed
+/// the quest earn the repeat share, or nothing if it is not repeatable. Their
+/// `UserProfile` and `QuestCompletions` PDAs for the location must be passed in
+/// `remaining_accounts`; `join_submission` creates them for participants and
+/// `initialize_completions` for party members.
+pub(crate) fn distribute_shared_rewards(
+    submission: &QuestSubmission,
+    quest: &Quest,
+    location_quests: &Pubkey,
+    remaining_accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    timestamp: i64,
+) -> Result<()> {
+    for member in &submission.party_members {
+        let Some(reward) = credit_member(
+            quest,
+            member,
+            location_quests,
+            remaining_accounts,
+            program_id,
+            timestamp,
+            XploraError::PartyMemberProfileMissing,
+        )? else {
+            msg!("Party member {} already completed this quest", member);
+            continue;
+        };
+        
+        emit!(PartyRewardEvent {
+            member: *member,
+            leader: submission.user,
+            location: submission.location.clone(),
+            quest_index: submission.quest_index,
+            xp_reward: reward.xp,
+            token_reward: reward.tokens,
+            new_level: reward.new_level,
+            timestamp,
+        });
+    }
+    
+    for participant in &submission.participants {
+        let Some(reward) = credit_member(
+            quest,
+            participant,
+            location_quests,
+            remaining_accounts,
+            program_id,
+            timestamp,
+            XploraError::ParticipantAccountsMissing,
+        )? else {
+            msg!("Participant {} already completed this quest", participant);
+            continue;
+        };
+        
+        emit!(ParticipantRewardEvent {
+            participant: *participant,
+            submitter: submission.user,
+            location: submission.location.clone(),
+            quest_index: submission.quest_index,
+            xp_reward: reward.xp,
+            token_reward: reward.tokens,
+            new_level: reward.new_level,
+            timestamp,
+        });
+    }
+    
+    if !submission.party_members.is_empty() {
+        msg!("Party rewards credited to {} members", submission.party_members.len());
+    }
+    if !submission.participants.is_empty() {
+        msg!("Rewards credited to {} participants", submission.participants.len());
+    }
+    
+    Ok(())
+}
+
+/// Rewards credited to one member
+struct MemberReward {
+    xp: u64,
+    tokens: u64,
+    new_level: u16,
+}
+
+/// Find a writable program account in `remaining_accounts` by its PDA seeds
+fn find_member_account<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+    missing: XploraError,
+) -> Result<&'a AccountInfo<'info>> {
+    let (expected, _) = Pubkey::find_program_address(seeds, program_id);
+    remaining_accounts.iter()
+        .find(|info| info.key() == expected && info.owner == program_id && info.is_writable)
+        .ok_or_else(|| missing.into())
+}
+
+/// Mark a quest completed in a member's completion bitmap for the location
+/// and credit the member's reward to their profile. Returns None, crediting
+/// nothing, if the member already completed a quest that is not repeatable.
+fn credit_member(
+    quest: &Quest,
+    member: &Pubkey,
+    location_quests: &Pubkey,
+    remaining_accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    timestamp: i64,
+    missing: XploraError,
+) -> Result<Option<MemberReward>> {
+    let completions_info = find_member_account(
+        remaining_accounts,
+        &[b"completions", member.as_ref(), location_quests.as_ref()],
+        program_id,
+        missing,
+    )?;
+    let profile_info = find_member_account(
+        remaining_accounts,
+        &[b"user_profile", member.as_ref()],
+        program_id,
+        missing,
+    )?;
+    
+    let mut completions_data = completions_info.try_borrow_mut_data()?;
+    let mut completions = QuestCompletions::try_deserialize(&mut &completions_data[..])?;
+    let is_repeat = completions.is_completed(quest.id);
+    if is_repeat && !quest.repeatable {
+        return Ok(None);
+    }
+    completions.mark_completed(quest.id);
+    completions.try_serialize(&mut &mut completions_data[..])?;
+    
+    let mut data = profile_info.try_borrow_mut_data()?;
+    let mut profile = UserProfile::try_deserialize(&mut &data[..])?;
+    let xp = quest_xp_reward(quest, is_repeat)?;
+    let mut tokens = quest_token_reward(quest, &profile);
+    if is_repeat {
+        tokens = repeat_reward(quest, tokens)?;
+    }
+    
+    profile.quests_completed = profile.quests_completed.checked_add(1)
+        .ok_or(XploraError::Overflow)?;
+    profile.total_tokens_earned = profile.total_tokens_earned.checked_add(tokens)
+        .ok_or(XploraError::Overflow)?;
+    profile.last_active = timestamp;
+    award_experience(&mut profile, xp)?;
+    update_streak(&mut profile, timestamp)?;
+    profile.try_serialize(&mut &mut data[..])?;
+    
+    Ok(Some(MemberReward { xp, tokens, new_level: profile.level }))
+}
+
+#[event]
+pub struct PartyCreatedEvent {
+    pub party: Pubkey,
+    pub leader: Pubkey,
+    pub max_size: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PartyMembershipEvent {
+    pub party: Pubkey,
+    pub member: Pubkey,
+    pub joined: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PartyRewardEvent {
+    pub member: Pubkey,
+    pub leader: Pubkey,
+    pub location: String,
+    pub quest_index: u8,
+    pub xp_reward: u64,
+    pub token_reward: u64,
+    pub new_level: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParticipantRewardEvent {
+    pub participant: Pubkey,
+    pub submitter: Pubkey,
+    pub location: String,
+    pub quest_index: u8,
+    pub xp_reward: u64,
+    pub token_reward: u64,
+    pub new_level: u16,
+    pub timestamp: i64,
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::XploraError;
+
+/// Return a quest's schedule status (Scheduled, Active or Expired) for clients
+pub fn get_quest_status(
+    ctx: Context<crate::GetQuestStatus>,
+    quest_index: u8,
+) -> Result<QuestStatus> {
+    let location_quests = &ctx.accounts.location_quests;
+    
+    // Validate quest index
+    require!(
+        (quest_index as usize) < location_quests.quests.len(),
+        XploraError::InvalidQuestIndex
+    );
+    
+    let clock = Clock::get()?;
+    Ok(location_quests.quests[quest_index as usize].quest_status(clock.unix_timestamp))
+}
@@ -0,0 +1,229 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::errors::XploraError;
+use crate::utils::{
+    check_quest_active, check_quest_eligibility, check_prerequisites, check_sponsorship,
+    quiz_answer_hash, quiz_commitment, record_sponsored_submission, record_submission_attempt,
+    transfer_lamports_from_pda,
+};
+use crate::instructions::approve_submission::settle_approval;
+use crate::instructions::reject_submission::settle_rejection;
+
+/// Commit to a quiz answer without revealing it
+pub fn commit_quiz_answer(
+    ctx: Context<crate::CommitQuizAnswer>,
+    _location: String,
+    quest_index: u8,
+    commitment: [u8; 32],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let location_quests = &ctx.accounts.location_quests;
+    
+    // Validate quest exists, is open and is a quiz
+    require!(
+        (quest_index as usize) < location_quests.quests.len(),
+        XploraError::InvalidQuestIndex
+    );
+    let quest = &location_quests.quests[quest_index as usize];
+    check_quest_active(quest, clock.unix_timestamp)?;
+    require!(
+        matches!(quest.quest_type, QuestType::Quiz { .. }),
+        XploraError::InvalidQuestType
+    );
+    
+    let quiz_commitment = &mut ctx.accounts.commitment;
+    quiz_commitment.user = ctx.accounts.user.key();
+    quiz_commitment.location_quests = location_quests.key();
+    quiz_commitment.quest_index = quest_index;
+    quiz_commitment.quest_id = quest.id;
+    quiz_commitment.commitment = commitment;
+    quiz_commitment.committed_slot = clock.slot;
+    quiz_commitment.bump = ctx.bumps.commitment;
+    
+    msg!("Quiz answer committed for quest {}", quest_index);
+    
+    Ok(())
+}
+
+/// Withdraw a quiz commitment and reclaim its rent, so a new answer can be committed
+pub fn cancel_quiz_commitment(ctx: Context<crate::CancelQuizCommitment>) -> Result<()> {
+    msg!("Quiz commitment withdrawn for quest {}", ctx.accounts.commitment.quest_index);
+    
+    Ok(())
+}
+
+/// Reveal a committed quiz answer. The answer is hashed on-chain and the
+/// submission is approved or rejected immediately, without a validator.
+/// The answer is not stored in any account, but it is readable from the
+/// reveal transaction and the salt is public, so this only deters casual
+/// copying. An incorrect answer may be committed and revealed again, up to
+/// `MAX_QUEST_ATTEMPTS` times.
+pub fn reveal_quiz_answer(
+    ctx: Context<crate::RevealQuizAnswer>,
+    location: String,
+    quest_index: u8,
+    repeat_epoch: u32,
+    answer: String,
+    nonce: [u8; 32],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let user = ctx.accounts.user.key();
+    let submission_key = ctx.accounts.submission.key();
+    let quest_id = ctx.accounts.commitment.quest_id;
+    let location_quests = &mut ctx.accounts.location_quests;
+    let location_quests_key = location_quests.key();
+    require!(
+        (quest_index as usize) < location_quests.quests.len(),
+        XploraError::InvalidQuestIndex
+    );
+    let quest = location_quests.quest_by_id_mut(quest_id)?;
+    
+    // Same gates as a regular submission
+    check_quest_active(quest, clock.unix_timestamp)?;
+    check_quest_eligibility(quest, &ctx.accounts.user_profile)?;
+    require!(
+        repeat_epoch == quest.repeat_epoch(clock.unix_timestamp),
+        XploraError::InvalidRepeatEpoch
+    );
+    
+    let completions = &mut ctx.accounts.completions;
+    if completions.user == Pubkey::default() {
+        completions.user = user;
+        completions.location_quests = location_quests_key;
+        completions.bump = ctx.bumps.completions;
+    }
+    require!(
+        !quest.is_full() || completions.is_completed(quest.id),
+        XploraError::QuestCapacityReached
+    );
+    check_prerequisites(
+        quest,
+        &user,
+        &location_quests_key,
+        completions,
+        ctx.remaining_accounts,
+        ctx.program_id,
+    )?;
+    
+    // The reveal must match a commitment made in an earlier slot
+    let commitment = &ctx.accounts.commitment;
+    require!(clock.slot > commitment.committed_slot, XploraError::RevealTooEarly);
+    require!(
+        quiz_commitment(&user, &nonce, &answer) == commitment.commitment,
+        XploraError::CommitmentMismatch
+    );
+    
+    let QuestType::Quiz { answer_hash, salt } = &quest.quest_type else {
+        return err!(XploraError::InvalidQuestType);
+    };
+    let correct = quiz_answer_hash(salt, &answer) == *answer_hash;
+    
+    // A rejected answer is retried at the same address as a new attempt
+    let submission = &mut ctx.accounts.submission;
+    let retry = submission.user != Pubkey::default();
+    require!(
+        !retry || submission.status == SubmissionStatus::Rejected,
+        XploraError::SubmissionAlreadyExists
+    );
+    submission.attempt_number = if retry {
+        submission.attempt_number.checked_add(1).ok_or(XploraError::Overflow)?
+    } else {
+        1
+    };
+    require!(
+        submission.attempt_number <= constants::MAX_QUEST_ATTEMPTS,
+        XploraError::MaxAttemptsReached
+    );
+    
+    // Wrong answers are honest rejections, so an unsettled deposit is refunded
+    let held_deposit = submission.deposit_lamports;
+    if held_deposit > 0 {
+        submission.deposit_lamports = 0;
+        transfer_lamports_from_pda(
+            &submission.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            held_deposit,
+        )?;
+    }
+    
+    // Other fields are zero-initialized by `init` or set when settling
+    submission.user = user;
+    submission.location = location;
+    submission.quest_index = quest_index;
+    submission.quest_id = quest.id;
+    submission.repeat_epoch = repeat_epoch;
+    submission.is_repeat = completions.is_completed(quest.id);
+    submission.submitted_at = clock.unix_timestamp;
+    submission.status = SubmissionStatus::Pending;
+    submission.rejection_reason = String::new();
+    submission.appeal_reason = String::new();
+    submission.appealed_at = 0;
+    submission.bump = ctx.bumps.submission;
+    
+    let profile = &mut ctx.accounts.user_profile;
+    
+    // Enforce the daily sponsored submission limit
+    let sponsored = check_sponsorship(
+        ctx.accounts.sponsor_policy.as_deref(),
+        &ctx.accounts.fee_payer.key(),
+        &user,
+    )?;
+    if sponsored {
+        record_sponsored_submission(
+            profile,
+            ctx.accounts.sponsor_policy.as_deref(),
+            clock.unix_timestamp,
+        )?;
+    }
+    
+    // Throttle like a regular submission; quizzes settle at once, so they
+    // never count as pending
+    record_submission_attempt(profile, &ctx.accounts.registry.config, clock.unix_timestamp)?;
+    
+    // Hold the anti-spam deposit; correct answers get it back immediately,
+    // incorrect ones through `settle_deposit`
+    let deposit = ctx.accounts.registry.config.submission_deposit_lamports;
+    if deposit > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: submission.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+        submission.deposit_lamports = deposit;
+    }
+    
+    profile.quests_attempted = profile.quests_attempted.checked_add(1)
+        .ok_or(XploraError::Overflow)?;
+    profile.last_active = clock.unix_timestamp;
+    
+    msg!("Quiz answer revealed: {}", if correct { "correct" } else { "incorrect" });
+    
+    // The program itself acts as the validator
+    if correct {
+        settle_approval(
+            submission,
+            &ctx.accounts.user.to_account_info(),
+            profile,
+            completions,
+            quest,
+            crate::ID,
+            0,
+        )?;
+    } else {
+        settle_rejection(
+            submission,
+            submission_key,
+            crate::ID,
+            String::from("Incorrect quiz answer"),
+            clock.unix_timestamp,
+        )?;
+    }
+    
+    Ok(())
+}
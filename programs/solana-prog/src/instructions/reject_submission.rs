@@ -1,13 +1,16 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::XploraError;
+use crate::instructions::reward_vault::pay_review_fee;
 
-/// Reject a quest submission
+/// Reject a quest submission. Fraudulent submissions forfeit their deposit.
 pub fn reject_submission(
     ctx: Context<crate::RejectSubmission>,
     reason: String,
+    fraud: bool,
 ) -> Result<()> {
     let clock = Clock::get()?;
+    let submission_key = ctx.accounts.submission.key();
     let submission = &mut ctx.accounts.submission;
     
     // Verify submission is pending
@@ -16,16 +19,51 @@ pub fn reject_submission(
         XploraError::SubmissionNotPending
     );
     
+    // Consensus quests are resolved by validator votes
+    let quest = ctx.accounts.location_quests.quest_by_id(submission.quest_id)?;
+    require!(quest.required_votes <= 1, XploraError::ConsensusRequired);
+    
     // Validate reason length
     require!(
-        !reason.is_empty() && reason.len() <= 200,
+        !reason.is_empty() && reason.len() <= QuestSubmission::MAX_REASON_LEN,
         XploraError::InvalidDescription
     );
     
+    settle_rejection(
+        submission,
+        submission_key,
+        ctx.accounts.validator.key(),
+        reason,
+        clock.unix_timestamp,
+    )?;
+    submission.flagged_fraud = fraud;
+    ctx.accounts.user_profile.release_pending_submission();
+    
+    pay_review_fee(
+        &mut ctx.accounts.reward_vault,
+        &mut ctx.accounts.validator_profile,
+        &ctx.accounts.validator.to_account_info(),
+        &ctx.accounts.registry.config,
+        submission_key,
+        clock.unix_timestamp,
+    )?;
+    
+    Ok(())
+}
+
+/// Mark a submission rejected and emit the rejection event
+pub(crate) fn settle_rejection(
+    submission: &mut QuestSubmission,
+    submission_key: Pubkey,
+    validator: Pubkey,
+    reason: String,
+    timestamp: i64,
+) -> Result<()> {
     // Update submission
     submission.status = SubmissionStatus::Rejected;
-    submission.validator = Some(ctx.accounts.validator.key());
-    submission.validated_at = Some(clock.unix_timestamp);
+    submission.validator = Some(validator);
+    submission.validated_at = Some(timestamp);
+    submission.rejection_reason = reason.clone();
     
     msg!("Quest submission rejected");
     msg!("Reason: {}", reason);
@@ -33,11 +71,11 @@ pub fn reject_submission(
     // Emit rejection event
     emit!(QuestRejectionEvent {
         user: submission.user,
-        submission_pda: submission.key(),
+        submission_pda: submission_key,
         location: submission.location.clone(),
         quest_index: submission.quest_index,
         reason,
-        timestamp: clock.unix_timestamp,
+        timestamp,
     });
     
     Ok(())
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::errors::XploraError;
+use crate::utils::transfer_lamports_from_pda;
+
+/// Create the vault that pays per-review validator fees
+pub fn initialize_reward_vault(ctx: Context<crate::InitializeRewardVault>) -> Result<()> {
+    let vault = &mut ctx.accounts.reward_vault;
+    vault.total_funded = 0;
+    vault.total_paid = 0;
+    vault.bump = ctx.bumps.reward_vault;
+    
+    msg!("Reward vault initialized: {}", vault.key());
+    
+    Ok(())
+}
+
+/// Deposit lamports into the reward vault
+pub fn fund_reward_vault(ctx: Context<crate::FundRewardVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, XploraError::InvalidAmount);
+    
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.reward_vault.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    
+    let vault = &mut ctx.accounts.reward_vault;
+    vault.total_funded = vault.total_funded.checked_add(amount)
+        .ok_or(XploraError::Overflow)?;
+    
+    msg!("Reward vault funded with {} lamports", amount);
+    
+    Ok(())
+}
+
+/// Record a direct review and pay the validator's fee from the vault.
+/// Only bonded validators are paid; reviews past the daily cap still count
+/// but earn no fee. Returns the fee paid.
+pub(crate) fn pay_review_fee<'info>(
+    vault: &mut Account<'info, RewardVault>,
+    validator_profile: &mut Account<'info, ValidatorProfile>,
+    validator: &AccountInfo<'info>,
+    config: &RegistryConfig,
+    submission_key: Pubkey,
+    timestamp: i64,
+) -> Result<u64> {
+    require!(
+        validator_profile.is_bonded(config.min_validator_stake),
+        XploraError::InsufficientStake
+    );
+    
+    validator_profile.reviews_completed = validator_profile.reviews_completed
+        .checked_add(1)
+        .ok_or(XploraError::Overflow)?;
+    
+    // Roll the daily counter over on a new day
+    let today = timestamp / constants::ONE_DAY_SECONDS;
+    if validator_profile.fee_day != today {
+        validator_profile.fee_day = today;
+        validator_profile.paid_reviews_today = 0;
+    }
+    
+    let fee = config.review_fee_lamports;
+    if fee == 0 || validator_profile.paid_reviews_today >= config.max_paid_reviews_per_day {
+        msg!("No review fee paid");
+        return Ok(0);
+    }
+    
+    // Keep the vault rent-exempt
+    let rent_floor = Rent::get()?.minimum_balance(RewardVault::LEN);
+    let available = vault.to_account_info().lamports().saturating_sub(rent_floor);
+    require!(available >= fee, XploraError::InsufficientVaultBalance);
+    
+    transfer_lamports_from_pda(&vault.to_account_info(), validator, fee)?;
+    
+    vault.total_paid = vault.total_paid.checked_add(fee)
+        .ok_or(XploraError::Overflow)?;
+    validator_profile.paid_reviews_today += 1;
+    validator_profile.review_fees_earned = validator_profile.review_fees_earned
+        .checked_add(fee)
+        .ok_or(XploraError::Overflow)?;
+    
+    msg!("Review fee paid: {} lamports", fee);
+    
+    emit!(ReviewFeePaidEvent {
+        validator: validator.key(),
+        submission_pda: submission_key,
+        amount: fee,
+        timestamp,
+    });
+    
+    Ok(fee)
+}
+
+#[event]
+pub struct ReviewFeePaidEvent {
+    pub validator: Pubkey,
+    pub submission_pda: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
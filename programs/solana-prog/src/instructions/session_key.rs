@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::XploraError;
+use crate::utils::{validate_location, check_sponsorship};
+
+/// Create a session key that can sign submissions on the owner's behalf
+pub fn create_session_key(
+    ctx: Context<crate::CreateSessionKey>,
+    session_key: Pubkey,
+    expires_at: i64,
+    scope: u8,
+    locations: Vec<String>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    // Validate expiry window
+    require!(
+        expires_at > clock.unix_timestamp
+            && expires_at - clock.unix_timestamp <= constants::MAX_SESSION_DURATION_SECONDS,
+        XploraError::InvalidSessionExpiry
+    );
+    
+    // Validate scope
+    require!(
+        scope != 0 && scope & !SessionKey::ALL_SCOPES == 0,
+        XploraError::SessionScopeNotAllowed
+    );
+    
+    // Validate location restrictions
+    require!(
+        locations.len() <= SessionKey::MAX_LOCATIONS,
+        XploraError::SessionLocationNotAllowed
+    );
+    for location in &locations {
+        validate_location(location)?;
+    }
+    
+    // Relayer-paid rent must come from an allowed relayer
+    check_sponsorship(
+        ctx.accounts.sponsor_policy.as_deref(),
+        &ctx.accounts.fee_payer.key(),
+        &ctx.accounts.owner.key(),
+    )?;
+    
+    let session = &mut ctx.accounts.session;
+    session.owner = ctx.accounts.owner.key();
+    session.session_key = session_key;
+    session.expires_at = expires_at;
+    session.scope = scope;
+    session.locations = locations;
+    session.created_at = clock.unix_timestamp;
+    session.bump = ctx.bumps.session;
+    
+    msg!("Session key {} created for: {}", session_key, session.owner);
+    msg!("Expires at: {}", expires_at);
+    
+    emit!(SessionKeyCreatedEvent {
+        owner: session.owner,
+        session_key,
+        expires_at,
+        scope,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Revoke a session key, closing its account
+pub fn revoke_session_key(ctx: Context<crate::RevokeSessionKey>) -> Result<()> {
+    let session = &ctx.accounts.session;
+    
+    msg!("Session key {} revoked for: {}", session.session_key, session.owner);
+    
+    emit!(SessionKeyRevokedEvent {
+        owner: session.owner,
+        session_key: session.session_key,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct SessionKeyCreatedEvent {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub expires_at: i64,
+    pub scope: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SessionKeyRevokedEvent {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::XploraError;
+use crate::utils::get_current_timestamp;
+
+/// Set the relayers allowed to sponsor user transactions
+pub fn set_sponsor_policy(
+    ctx: Context<crate::SetSponsorPolicy>,
+    relayers: Vec<Pubkey>,
+    max_sponsored_per_day: u16,
+) -> Result<()> {
+    require!(
+        relayers.len() <= SponsorPolicy::MAX_RELAYERS,
+        XploraError::TooManyRelayers
+    );
+    
+    let policy = &mut ctx.accounts.sponsor_policy;
+    policy.relayers = relayers;
+    policy.max_sponsored_per_day = max_sponsored_per_day;
+    policy.updated_at = get_current_timestamp();
+    policy.bump = ctx.bumps.sponsor_policy;
+    
+    msg!("Sponsor policy updated");
+    msg!("Relayers: {}, daily limit: {}", policy.relayers.len(), max_sponsored_per_day);
+    
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::errors::XploraError;
+use crate::utils::transfer_lamports_from_pda;
+
+/// Bond lamports into the validator's PDA
+pub fn stake_validator(ctx: Context<crate::StakeValidator>, amount: u64) -> Result<()> {
+    require!(amount > 0, XploraError::InvalidStakeAmount);
+    
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.validator.to_account_info(),
+                to: ctx.accounts.validator_profile.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    
+    let profile = &mut ctx.accounts.validator_profile;
+    profile.staked_lamports = profile.staked_lamports.checked_add(amount)
+        .ok_or(XploraError::Overflow)?;
+    
+    msg!("Validator {} staked {} lamports", profile.validator, amount);
+    msg!("Total stake: {}", profile.staked_lamports);
+    
+    Ok(())
+}
+
+/// Move bonded stake into unbonding; it stays slashable until withdrawn
+pub fn request_unstake(ctx: Context<crate::RequestUnstake>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let profile = &mut ctx.accounts.validator_profile;
+    
+    require!(
+        amount > 0 && amount <= profile.staked_lamports,
+        XploraError::InvalidStakeAmount
+    );
+    
+    profile.staked_lamports -= amount;
+    profile.unbonding_lamports = profile.unbonding_lamports.checked_add(amount)
+        .ok_or(XploraError::Overflow)?;
+    profile.unbonding_started_at = clock.unix_timestamp;
+    
+    msg!("Validator {} unbonding {} lamports", profile.validator, amount);
+    
+    Ok(())
+}
+
+/// Withdraw stake once the unbonding period has passed
+pub fn withdraw_stake(ctx: Context<crate::WithdrawStake>) -> Result<()> {
+    let clock = Clock::get()?;
+    let unbonding_period = ctx.accounts.registry.config.unbonding_period_seconds;
+    let profile = &mut ctx.accounts.validator_profile;
+    
+    let amount = profile.unbonding_lamports;
+    require!(amount > 0, XploraError::InvalidStakeAmount);
+    require!(profile.open_challenges == 0, XploraError::ChallengesOpen);
+    let unbonded_at = profile.unbonding_started_at.checked_add(unbonding_period)
+        .ok_or(XploraError::Overflow)?;
+    require!(
+        clock.unix_timestamp >= unbonded_at,
+        XploraError::UnbondingNotComplete
+    );
+    
+    profile.unbonding_lamports = 0;
+    transfer_lamports_from_pda(
+        &profile.to_account_info(),
+        &ctx.accounts.validator.to_account_info(),
+        amount,
+    )?;
+    
+    msg!("Validator {} withdrew {} lamports", profile.validator, amount);
+    
+    Ok(())
+}
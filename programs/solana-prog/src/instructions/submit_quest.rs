@@ -1,31 +1,134 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use crate::state::*;
 use crate::errors::XploraError;
+use crate::utils::{
+    validate_evidence, validate_location_proof, verify_location_attestation, check_sponsorship,
+    verify_user_signer, check_prerequisites, check_quest_eligibility, check_quest_type_completion,
+    check_quest_active, record_sponsored_submission, record_submission_attempt,
+};
+use crate::instructions::initialize_profile::{init_profile_fields, ProfileCreatedEvent};
+use crate::instructions::party::check_party_members;
 
-/// Submit a quest completion with IPFS photo hash
+/// Submit a quest completion with IPFS evidence attachments
 pub fn submit_quest_completion(
     ctx: Context<crate::SubmitQuestCompletion>,
     location: String,
     quest_index: u8,
-    ipfs_hash: String,
+    repeat_epoch: u32,
+    evidence: Vec<EvidenceAttachment>,
     description: String,
+    location_proof: LocationProof,
 ) -> Result<()> {
     let clock = Clock::get()?;
     let submission = &mut ctx.accounts.submission;
     let location_quests = &ctx.accounts.location_quests;
     
+    // An expired submission is reopened as a new attempt at the same address
+    let resubmission = submission.user != Pubkey::default();
+    require!(
+        !resubmission || submission.status == SubmissionStatus::Expired,
+        XploraError::SubmissionAlreadyExists
+    );
+    
+    // Signer must be the user or one of its session keys
+    verify_user_signer(
+        &ctx.accounts.user.key(),
+        &ctx.accounts.signer.key(),
+        ctx.accounts.session_key.as_deref(),
+        SessionKey::SCOPE_SUBMIT,
+        &location,
+        clock.unix_timestamp,
+    )?;
+    
     // Validate quest exists
     require!(
         (quest_index as usize) < location_quests.quests.len(),
         XploraError::InvalidQuestIndex
     );
     
-    // Validate IPFS hash format (basic check)
+    // Only active quests take submissions
+    check_quest_active(&location_quests.quests[quest_index as usize], clock.unix_timestamp)?;
+    
+    // Repeatable quests take one submission per cooldown window
+    require!(
+        repeat_epoch == location_quests.quests[quest_index as usize].repeat_epoch(clock.unix_timestamp),
+        XploraError::InvalidRepeatEpoch
+    );
+    
+    // Create the completion bitmap on first submission at this location
+    let completions = &mut ctx.accounts.completions;
+    if completions.user == Pubkey::default() {
+        completions.user = ctx.accounts.user.key();
+        completions.location_quests = location_quests.key();
+        completions.bump = ctx.bumps.completions;
+    }
+    
+    // One-shot quests cannot be submitted again once completed
+    let quest_id = location_quests.quests[quest_index as usize].id;
+    require!(
+        location_quests.quests[quest_index as usize].repeatable || !completions.is_completed(quest_id),
+        XploraError::QuestAlreadyCompleted
+    );
+    
+    // Sold-out quests take no new submissions; repeats do not use up capacity
     require!(
-        ipfs_hash.len() >= 46 && ipfs_hash.starts_with("Qm"),
-        XploraError::InvalidIpfsHash
+        !location_quests.quests[quest_index as usize].is_full() || completions.is_completed(quest_id),
+        XploraError::QuestCapacityReached
     );
     
+    // Storyline quests require their prerequisites to be completed first
+    check_prerequisites(
+        &location_quests.quests[quest_index as usize],
+        &ctx.accounts.user.key(),
+        &location_quests.key(),
+        completions,
+        ctx.remaining_accounts,
+        ctx.program_id,
+    )?;
+    
+    // Every party member must be able to take on the quest as well
+    if let Some(party) = ctx.accounts.party.as_ref() {
+        check_party_members(
+            &party.members,
+            &location_quests.quests[quest_index as usize],
+            &location_quests.key(),
+            ctx.remaining_accounts,
+            ctx.program_id,
+        )?;
+    }
+    
+    // Check the claimed GPS fix against the quest coordinates
+    let distance_m = validate_location_proof(
+        &location_proof,
+        &location_quests.quests[quest_index as usize],
+        &ctx.accounts.registry.config,
+        clock.unix_timestamp,
+    )?;
+    
+    // A preceding Ed25519 instruction must be a valid oracle attestation of this fix
+    let location_attested = verify_location_attestation(
+        &ctx.accounts.instructions_sysvar,
+        &location_proof,
+        &ctx.accounts.user.key(),
+        &location_quests.key(),
+        quest_index,
+        &ctx.accounts.registry.config.location_oracle,
+    )?;
+    require!(
+        location_attested || !location_quests.quests[quest_index as usize].requires_location_attestation,
+        XploraError::LocationAttestationRequired
+    );
+    
+    // Trails and scavenger hunts have their own completion requirements
+    check_quest_type_completion(
+        &location_quests.quests[quest_index as usize],
+        ctx.accounts.progress.as_deref(),
+    )?;
+    
+    // Validate attachments and parse their CIDs into binary form
+    let stored_evidence = validate_evidence(&evidence)?;
+    
     // Validate description length
     require!(
         description.len() <= QuestSubmission::MAX_DESCRIPTION_LEN,
@@ -36,25 +139,113 @@ pub fn submit_quest_completion(
     submission.user = ctx.accounts.user.key();
     submission.location = location.clone();
     submission.quest_index = quest_index;
-    submission.ipfs_hash = ipfs_hash.clone();
+    submission.repeat_epoch = repeat_epoch;
+    submission.participants = Vec::new();
+    submission.party_members = ctx.accounts.party.as_ref()
+        .map(|party| party.members.clone())
+        .unwrap_or_default();
+    submission.quest_id = location_quests.quests[quest_index as usize].id;
+    submission.is_repeat = completions.is_completed(submission.quest_id);
+    submission.evidence = stored_evidence;
     submission.description = description;
     submission.submitted_at = clock.unix_timestamp;
     submission.status = SubmissionStatus::Pending;
     submission.validator = None;
     submission.validated_at = None;
+    submission.location_proof = location_proof;
+    submission.distance_m = distance_m;
+    submission.location_attested = location_attested;
+    submission.ai_verdict = None;
+    submission.approve_votes = 0;
+    submission.reject_votes = 0;
+    submission.fraud_votes = 0;
+    submission.validator_reward_pool = 0;
+    submission.rejection_reason = String::new();
+    submission.appeal_reason = String::new();
+    submission.appealed_at = 0;
     submission.reward_amount = 0;
-    submission.attempt_number = 1; // Can be enhanced to track multiple attempts
+    submission.xp_reward = 0;
+    submission.attempt_number = if resubmission {
+        submission.attempt_number.checked_add(1).ok_or(XploraError::Overflow)?
+    } else {
+        1
+    };
+    require!(
+        submission.attempt_number <= constants::MAX_QUEST_ATTEMPTS,
+        XploraError::MaxAttemptsReached
+    );
+    submission.deposit_lamports = 0;
+    submission.flagged_fraud = false;
     submission.bump = ctx.bumps.submission;
     
-    // Update user profile attempts
+    // Create a default profile on first submission
     let profile = &mut ctx.accounts.user_profile;
+    if profile.user == Pubkey::default() {
+        init_profile_fields(
+            profile,
+            ctx.accounts.user.key(),
+            String::new(),
+            ctx.bumps.user_profile,
+            clock.unix_timestamp,
+        );
+        
+        msg!("User profile created for: {}", ctx.accounts.user.key());
+        
+        emit!(ProfileCreatedEvent {
+            user: ctx.accounts.user.key(),
+            username: String::new(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    
+    // Gate quests reserved for experienced explorers
+    check_quest_eligibility(&location_quests.quests[quest_index as usize], profile)?;
+    
+    // Enforce the daily sponsored submission limit
+    let sponsored = check_sponsorship(
+        ctx.accounts.sponsor_policy.as_deref(),
+        &ctx.accounts.fee_payer.key(),
+        &ctx.accounts.signer.key(),
+    )?;
+    if sponsored {
+        record_sponsored_submission(
+            profile,
+            ctx.accounts.sponsor_policy.as_deref(),
+            clock.unix_timestamp,
+        )?;
+    }
+    
+    // Hold the anti-spam deposit in the submission account; the user pays it
+    // even when a relayer sponsors the fees
+    let deposit = ctx.accounts.registry.config.submission_deposit_lamports;
+    if deposit > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.signer.to_account_info(),
+                    to: submission.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+        submission.deposit_lamports = deposit;
+    }
+    
+    // Throttle pending and rolling-24h submissions
+    record_submission_attempt(profile, &ctx.accounts.registry.config, clock.unix_timestamp)?;
+    profile.pending_submissions = profile.pending_submissions.checked_add(1)
+        .ok_or(XploraError::Overflow)?;
+    
+    // Update user profile attempts
     profile.quests_attempted = profile.quests_attempted.checked_add(1)
         .ok_or(XploraError::Overflow)?;
     profile.last_active = clock.unix_timestamp;
     
     msg!("Quest submission created for user: {}", ctx.accounts.user.key());
     msg!("Location: {}, Quest: {}", location, quest_index);
-    msg!("IPFS Hash: {}", ipfs_hash);
+    msg!("Evidence attachments: {}", evidence.len());
+    msg!("Distance from quest: {}m", distance_m);
     
     // Emit event for backend monitoring
     emit!(QuestSubmissionEvent {
@@ -62,7 +253,9 @@ pub fn submit_quest_completion(
         submission_pda: submission.key(),
         location: location.clone(),
         quest_index,
-        ipfs_hash,
+        evidence,
+        distance_m,
+        location_attested,
         timestamp: clock.unix_timestamp,
     });
     
@@ -75,7 +268,9 @@ pub struct QuestSubmissionEvent {
     pub submission_pda: Pubkey,
     pub location: String,
     pub quest_index: u8,
-    pub ipfs_hash: String,
+    pub evidence: Vec<EvidenceAttachment>,
+    pub distance_m: u32,
+    pub location_attested: bool,
     pub timestamp: i64,
 }
 
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// Create the program treasury that collects slashed stake and forfeits
+pub fn initialize_treasury(ctx: Context<crate::InitializeTreasury>) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_collected = 0;
+    treasury.bump = ctx.bumps.treasury;
+    
+    msg!("Treasury initialized: {}", treasury.key());
+    
+    Ok(())
+}
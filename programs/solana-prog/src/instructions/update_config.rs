@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::XploraError;
+
+/// Update the program settings stored on the registry
+pub fn update_registry_config(
+    ctx: Context<crate::UpdateRegistryConfig>,
+    config: RegistryConfig,
+) -> Result<()> {
+    require!(
+        config.max_submissions_per_day as usize <= UserProfile::SUBMISSION_HISTORY_LEN,
+        XploraError::InvalidConfig
+    );
+    
+    // Basis points cannot exceed 100%
+    require!(
+        config.min_ai_confidence_bps as u64 <= constants::BPS_DENOMINATOR,
+        XploraError::InvalidConfig
+    );
+    
+    // Windows and deadlines are durations
+    require!(
+        config.appeal_window_seconds >= 0
+            && config.unbonding_period_seconds >= 0
+            && config.review_sla_seconds >= 0,
+        XploraError::InvalidConfig
+    );
+    
+    // Stake must stay slashable for as long as its decisions can be challenged
+    require!(
+        config.unbonding_period_seconds >= constants::CHALLENGE_WINDOW_SECONDS,
+        XploraError::InvalidConfig
+    );
+    
+    let registry = &mut ctx.accounts.registry;
+    registry.config = config;
+    
+    msg!("Registry config updated: {:?}", registry.config);
+    
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::XploraError;
+use crate::utils::validate_ipfs_hash;
+
+/// Update profile avatar and bio
+pub fn update_user_profile(
+    ctx: Context<crate::UpdateUserProfile>,
+    avatar_cid: String,
+    bio: String,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let profile = &mut ctx.accounts.user_profile;
+    
+    // Empty avatar clears it
+    if !avatar_cid.is_empty() {
+        validate_ipfs_hash(&avatar_cid)?;
+    }
+    
+    // Validate bio length
+    require!(
+        bio.len() <= UserProfile::MAX_BIO_LEN,
+        XploraError::InvalidBio
+    );
+    
+    profile.avatar_cid = avatar_cid.clone();
+    profile.bio = bio.clone();
+    profile.last_active = clock.unix_timestamp;
+    
+    msg!("Profile updated for: {}", profile.user);
+    
+    emit!(ProfileUpdatedEvent {
+        user: profile.user,
+        avatar_cid,
+        bio,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct ProfileUpdatedEvent {
+    pub user: Pubkey,
+    pub avatar_cid: String,
+    pub bio: String,
+    pub timestamp: i64,
+}
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::XploraError;
-use crate::utils::{validate_quest, get_current_timestamp};
+use crate::utils::{validate_quest, validate_prerequisites, get_current_timestamp};
 
 /// Update an existing quest
 pub fn update_quest(
@@ -9,6 +9,7 @@ pub fn update_quest(
     quest_index: u8,
     updated_quest: Quest,
 ) -> Result<()> {
+    let location_key = ctx.accounts.location_quests.key();
     let location_quests = &mut ctx.accounts.location_quests;
     
     // Check if location is initialized
@@ -23,10 +24,18 @@ pub fn update_quest(
     // Validate updated quest data
     validate_quest(&updated_quest)?;
 
-    // Preserve original creation timestamp
-    let original_created_at = location_quests.quests[quest_index as usize].created_at;
+    // Preserve original creation timestamp, id and completion count
+    let original = &location_quests.quests[quest_index as usize];
     let mut quest_with_timestamp = updated_quest;
-    quest_with_timestamp.created_at = original_created_at;
+    quest_with_timestamp.created_at = original.created_at;
+    quest_with_timestamp.id = original.id;
+    quest_with_timestamp.completions = original.completions;
+    require!(
+        quest_with_timestamp.max_completions == 0
+            || quest_with_timestamp.max_completions >= quest_with_timestamp.completions,
+        XploraError::InvalidCapacity
+    );
+    validate_prerequisites(&quest_with_timestamp, &location_key, location_quests)?;
 
     // Update quest
     location_quests.quests[quest_index as usize] = quest_with_timestamp;
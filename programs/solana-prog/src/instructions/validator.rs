@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+/// Register a validator allowed to vote on consensus submissions
+pub fn register_validator(
+    ctx: Context<crate::RegisterValidator>,
+    validator: Pubkey,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let profile = &mut ctx.accounts.validator_profile;
+    
+    profile.validator = validator;
+    profile.active = true;
+    profile.votes_cast = 0;
+    profile.agreeing_votes = 0;
+    profile.tokens_earned = 0;
+    profile.staked_lamports = 0;
+    profile.unbonding_lamports = 0;
+    profile.unbonding_started_at = 0;
+    profile.slashed_lamports = 0;
+    profile.reviews_completed = 0;
+    profile.review_fees_earned = 0;
+    profile.fee_day = 0;
+    profile.paid_reviews_today = 0;
+    profile.open_challenges = 0;
+    profile.registered_at = clock.unix_timestamp;
+    profile.bump = ctx.bumps.validator_profile;
+    
+    msg!("Validator registered: {}", validator);
+    
+    Ok(())
+}
+
+/// Enable or disable a validator's voting rights
+pub fn set_validator_active(
+    ctx: Context<crate::SetValidatorActive>,
+    active: bool,
+) -> Result<()> {
+    let profile = &mut ctx.accounts.validator_profile;
+    profile.active = active;
+    
+    msg!("Validator {} active: {}", profile.validator, active);
+    
+    Ok(())
+}
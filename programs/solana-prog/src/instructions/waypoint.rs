@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::XploraError;
+use crate::utils::{validate_location_fix, verify_location_attestation, check_quest_active};
+use crate::instructions::approve_submission::award_experience;
+use crate::instructions::initialize_profile::{init_profile_fields, ProfileCreatedEvent};
+
+/// Check in at a waypoint of a trail or scavenger quest with a GPS fix.
+/// Trail waypoints must be visited in order; scavenger landmarks in any order.
+/// Each check-in awaits validator review before it counts.
+pub fn visit_waypoint(
+    ctx: Context<crate::VisitWaypoint>,
+    _location: String,
+    quest_index: u8,
+    waypoint_index: u8,
+    location_proof: LocationProof,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let location_quests = &ctx.accounts.location_quests;
+    
+    // Validate quest exists and is open
+    require!(
+        (quest_index as usize) < location_quests.quests.len(),
+        XploraError::InvalidQuestIndex
+    );
+    let quest = &location_quests.quests[quest_index as usize];
+    check_quest_active(quest, clock.unix_timestamp)?;
+    
+    let waypoints = quest.quest_type.waypoints().ok_or(XploraError::InvalidQuestType)?;
+    let index = waypoint_index as usize;
+    require!(index < waypoints.len(), XploraError::InvalidWaypoint);
+    
+    let progress = &mut ctx.accounts.progress;
+    if progress.user == Pubkey::default() {
+        progress.user = ctx.accounts.user.key();
+        progress.location_quests = location_quests.key();
+        progress.quest_index = quest_index;
+        progress.quest_id = quest.id;
+        progress.bump = ctx.bumps.progress;
+    }
+    
+    // Progress left behind by a deleted quest does not carry over to its successor
+    require!(progress.quest_id == quest.id, XploraError::QuestNotFound);
+    
+    require!(!progress.is_visited(index), XploraError::WaypointAlreadyVisited);
+    if let QuestType::Trail { .. } = quest.quest_type {
+        require!(
+            (0..index).all(|previous| progress.is_visited(previous)),
+            XploraError::WaypointOutOfOrder
+        );
+    }
+    
+    // Check the GPS fix against the waypoint
+    let distance_m = validate_location_fix(
+        &location_proof,
+        &waypoints[index],
+        &ctx.accounts.registry.config,
+        clock.unix_timestamp,
+    )?;
+    let location_attested = verify_location_attestation(
+        &ctx.accounts.instructions_sysvar,
+        &location_proof,
+        &ctx.accounts.user.key(),
+        &location_quests.key(),
+        quest_index,
+        &ctx.accounts.registry.config.location_oracle,
+    )?;
+    require!(
+        location_attested || !quest.requires_location_attestation,
+        XploraError::LocationAttestationRequired
+    );
+    
+    progress.visited |= 1 << index;
+    progress.visit_count += 1;
+    progress.last_visit_at = clock.unix_timestamp;
+    
+    // Create a default profile on first check-in so approvals can award XP
+    let profile = &mut ctx.accounts.user_profile;
+    if profile.user == Pubkey::default() {
+        init_profile_fields(
+            profile,
+            ctx.accounts.user.key(),
+            String::new(),
+            ctx.bumps.user_profile,
+            clock.unix_timestamp,
+        );
+        
+        emit!(ProfileCreatedEvent {
+            user: ctx.accounts.user.key(),
+            username: String::new(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    profile.last_active = clock.unix_timestamp;
+    
+    msg!("Waypoint {} visited ({}m away)", waypoint_index, distance_m);
+    msg!("Progress: {}/{}", progress.visit_count, waypoints.len());
+    
+    emit!(WaypointVisitedEvent {
+        user: progress.user,
+        location_quests: progress.location_quests,
+        quest_index,
+        waypoint_index,
+        visit_count: progress.visit_count,
+        distance_m,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Review a pending check-in. Approved checkpoints earn partial XP;
+/// rejected ones can be visited again. Trail checkpoints are approved in
+/// order, and rejecting one also clears the later visits that depended on it.
+pub fn review_checkpoint(
+    ctx: Context<crate::ReviewCheckpoint>,
+    waypoint_index: u8,
+    approve: bool,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let progress = &mut ctx.accounts.progress;
+    let index = waypoint_index as usize;
+    
+    require!(
+        index < QuestType::MAX_WAYPOINTS && progress.is_visited(index) && !progress.is_approved(index),
+        XploraError::CheckpointNotPending
+    );
+    
+    let quest = ctx.accounts.location_quests.quest_by_id(progress.quest_id)?;
+    let is_trail = matches!(quest.quest_type, QuestType::Trail { .. });
+    require!(
+        !approve || !is_trail || (0..index).all(|previous| progress.is_approved(previous)),
+        XploraError::CheckpointOutOfOrder
+    );
+    
+    if approve {
+        progress.approved |= 1 << index;
+        progress.approved_count += 1;
+        progress.xp_earned = progress.xp_earned.checked_add(constants::CHECKPOINT_XP_REWARD)
+            .ok_or(XploraError::Overflow)?;
+        
+        let profile = &mut ctx.accounts.user_profile;
+        award_experience(profile, constants::CHECKPOINT_XP_REWARD)?;
+        profile.last_active = clock.unix_timestamp;
+    } else if is_trail {
+        // Later trail visits were only allowed because this one counted;
+        // they cannot have been approved yet
+        progress.visited &= (1 << index) - 1;
+        progress.visit_count = progress.visited.count_ones() as u8;
+    } else {
+        progress.visited &= !(1 << index);
+        progress.visit_count -= 1;
+    }
+    
+    msg!("Checkpoint {} {}", waypoint_index, if approve { "approved" } else { "rejected" });
+    msg!("Approved checkpoints: {}", progress.approved_count);
+    
+    emit!(CheckpointReviewedEvent {
+        user: progress.user,
+        location_quests: progress.location_quests,
+        quest_index: progress.quest_index,
+        waypoint_index,
+        approved: approve,
+        validator: ctx.accounts.validator.key(),
+        xp_reward: if approve { constants::CHECKPOINT_XP_REWARD } else { 0 },
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct WaypointVisitedEvent {
+    pub user: Pubkey,
+    pub location_quests: Pubkey,
+    pub quest_index: u8,
+    pub waypoint_index: u8,
+    pub visit_count: u8,
+    pub distance_m: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CheckpointReviewedEvent {
+    pub user: Pubkey,
+    pub location_quests: Pubkey,
+    pub quest_index: u8,
+    pub waypoint_index: u8,
+    pub approved: bool,
+    pub validator: Pubkey,
+    pub xp_reward: u64,
+    pub timestamp: i64,
+}
@@ -9,6 +9,7 @@ pub mod instructions;
 // Re-exports for convenience
 pub use errors::XploraError;
 pub use state::*;
+use utils::normalize_username;
 
 declare_id!("3rD6xKajAwvt8xbN5tkSSM8CvftGDs5x9jinkCK4BCCj");
 
@@ -55,6 +56,31 @@ pub mod xplora_quests {
         instructions::delete_quest::delete_quest(ctx, quest_index)
     }
 
+    /// Get a quest's schedule status
+    pub fn get_quest_status(
+        ctx: Context<GetQuestStatus>,
+        quest_index: u8,
+    ) -> Result<QuestStatus> {
+        instructions::quest_status::get_quest_status(ctx, quest_index)
+    }
+
+    /// Update program settings stored on the registry
+    pub fn update_registry_config(
+        ctx: Context<UpdateRegistryConfig>,
+        config: RegistryConfig,
+    ) -> Result<()> {
+        instructions::update_config::update_registry_config(ctx, config)
+    }
+
+    /// Set the relayers allowed to pay fees and rent for users
+    pub fn set_sponsor_policy(
+        ctx: Context<SetSponsorPolicy>,
+        relayers: Vec<Pubkey>,
+        max_sponsored_per_day: u16,
+    ) -> Result<()> {
+        instructions::sponsor_policy::set_sponsor_policy(ctx, relayers, max_sponsored_per_day)
+    }
+
     /// Initialize a user profile
     pub fn initialize_user_profile(
         ctx: Context<InitializeUserProfile>,
@@ -63,20 +89,58 @@ pub mod xplora_quests {
         instructions::initialize_profile::initialize_user_profile(ctx, username)
     }
 
-    /// Submit a quest completion with IPFS photo hash
+    /// Update profile avatar and bio
+    pub fn update_user_profile(
+        ctx: Context<UpdateUserProfile>,
+        avatar_cid: String,
+        bio: String,
+    ) -> Result<()> {
+        instructions::update_profile::update_user_profile(ctx, avatar_cid, bio)
+    }
+
+    /// Change the profile username, releasing the old handle
+    pub fn change_username(
+        ctx: Context<ChangeUsername>,
+        username: String,
+    ) -> Result<()> {
+        instructions::change_username::change_username(ctx, username)
+    }
+
+    /// Create a session key allowed to sign submissions for the owner
+    pub fn create_session_key(
+        ctx: Context<CreateSessionKey>,
+        session_key: Pubkey,
+        expires_at: i64,
+        scope: u8,
+        locations: Vec<String>,
+    ) -> Result<()> {
+        instructions::session_key::create_session_key(ctx, session_key, expires_at, scope, locations)
+    }
+
+    /// Revoke a session key
+    pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+        instructions::session_key::revoke_session_key(ctx)
+    }
+
+    /// Submit a quest completion with IPFS evidence attachments, creating
+    /// the user profile on first submission
     pub fn submit_quest_completion(
         ctx: Context<SubmitQuestCompletion>,
         location: String,
         quest_index: u8,
-        ipfs_hash: String,
+        repeat_epoch: u32,
+        evidence: Vec<EvidenceAttachment>,
         description: String,
+        location_proof: LocationProof,
     ) -> Result<()> {
         instructions::submit_quest::submit_quest_completion(
             ctx,
             location,
             quest_index,
-            ipfs_hash,
+            repeat_epoch,
+            evidence,
             description,
+            location_proof,
         )
     }
 
@@ -85,12 +149,243 @@ pub mod xplora_quests {
         instructions::approve_submission::approve_submission(ctx)
     }
 
-    /// Reject a quest submission
+    /// Approve a submission with a verdict signed by the AI validator
+    pub fn approve_with_attestation(
+        ctx: Context<ApproveWithAttestation>,
+        confidence_bps: u16,
+        model_version: String,
+        signed_at: i64,
+    ) -> Result<()> {
+        instructions::approve_with_attestation::approve_with_attestation(
+            ctx,
+            confidence_bps,
+            model_version,
+            signed_at,
+        )
+    }
+
+    /// Reject a quest submission, optionally flagging it as fraud
     pub fn reject_submission(
         ctx: Context<RejectSubmission>,
         reason: String,
+        fraud: bool,
+    ) -> Result<()> {
+        instructions::reject_submission::reject_submission(ctx, reason, fraud)
+    }
+
+    /// Appeal a rejected submission
+    pub fn appeal_submission(
+        ctx: Context<AppealSubmission>,
+        reason: String,
+    ) -> Result<()> {
+        instructions::appeal::appeal_submission(ctx, reason)
+    }
+
+    /// Resolve an appeal, optionally overturning the rejection
+    pub fn resolve_appeal(ctx: Context<ResolveAppeal>, overturn: bool) -> Result<()> {
+        instructions::appeal::resolve_appeal(ctx, overturn)
+    }
+
+    /// Register a consensus validator
+    pub fn register_validator(
+        ctx: Context<RegisterValidator>,
+        validator: Pubkey,
+    ) -> Result<()> {
+        instructions::validator::register_validator(ctx, validator)
+    }
+
+    /// Enable or disable a consensus validator
+    pub fn set_validator_active(
+        ctx: Context<SetValidatorActive>,
+        active: bool,
+    ) -> Result<()> {
+        instructions::validator::set_validator_active(ctx, active)
+    }
+
+    /// Cast a validator vote on a consensus submission; reject votes may
+    /// also flag it as fraudulent
+    pub fn cast_vote(ctx: Context<CastVote>, approve: bool, fraud: bool) -> Result<()> {
+        instructions::cast_vote::cast_vote(ctx, approve, fraud)
+    }
+
+    /// Claim a validator's share of a consensus reward pool
+    pub fn claim_vote_reward(ctx: Context<ClaimVoteReward>) -> Result<()> {
+        instructions::cast_vote::claim_vote_reward(ctx)
+    }
+
+    /// Initialize the treasury that collects slashed stake
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        instructions::treasury::initialize_treasury(ctx)
+    }
+
+    /// Bond lamports as validator stake
+    pub fn stake_validator(ctx: Context<StakeValidator>, amount: u64) -> Result<()> {
+        instructions::staking::stake_validator(ctx, amount)
+    }
+
+    /// Begin unbonding part of a validator's stake
+    pub fn request_unstake(ctx: Context<RequestUnstake>, amount: u64) -> Result<()> {
+        instructions::staking::request_unstake(ctx, amount)
+    }
+
+    /// Withdraw unbonded stake after the unbonding period
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>) -> Result<()> {
+        instructions::staking::withdraw_stake(ctx)
+    }
+
+    /// Challenge a validator's review decision
+    pub fn challenge_review(ctx: Context<ChallengeReview>, reason: String) -> Result<()> {
+        instructions::challenge::challenge_review(ctx, reason)
+    }
+
+    /// Uphold a challenge and slash the validator's stake
+    pub fn slash_validator(ctx: Context<SlashValidator>, amount: u64) -> Result<()> {
+        instructions::challenge::slash_validator(ctx, amount)
+    }
+
+    /// Dismiss a challenge without slashing
+    pub fn dismiss_challenge(ctx: Context<DismissChallenge>) -> Result<()> {
+        instructions::challenge::dismiss_challenge(ctx)
+    }
+
+    /// Initialize the vault that pays review fees
+    pub fn initialize_reward_vault(ctx: Context<InitializeRewardVault>) -> Result<()> {
+        instructions::reward_vault::initialize_reward_vault(ctx)
+    }
+
+    /// Deposit lamports into the reward vault
+    pub fn fund_reward_vault(ctx: Context<FundRewardVault>, amount: u64) -> Result<()> {
+        instructions::reward_vault::fund_reward_vault(ctx, amount)
+    }
+
+    /// Expire a submission left pending past the review SLA
+    pub fn expire_submission(ctx: Context<ExpireSubmission>) -> Result<()> {
+        instructions::expire_submission::expire_submission(ctx)
+    }
+
+    /// Refund or forfeit a submission's deposit once its outcome is final
+    pub fn settle_deposit(ctx: Context<SettleDeposit>) -> Result<()> {
+        instructions::deposit::settle_deposit(ctx)
+    }
+
+    /// Check in at a trail waypoint or scavenger landmark
+    pub fn visit_waypoint(
+        ctx: Context<VisitWaypoint>,
+        location: String,
+        quest_index: u8,
+        waypoint_index: u8,
+        location_proof: LocationProof,
+    ) -> Result<()> {
+        instructions::waypoint::visit_waypoint(
+            ctx,
+            location,
+            quest_index,
+            waypoint_index,
+            location_proof,
+        )
+    }
+
+    /// Co-submit a pending social quest submission
+    pub fn join_submission(
+        ctx: Context<JoinSubmission>,
+        location_proof: LocationProof,
+    ) -> Result<()> {
+        instructions::join_submission::join_submission(ctx, location_proof)
+    }
+
+    /// Approve or reject a trail or scavenger check-in
+    pub fn review_checkpoint(
+        ctx: Context<ReviewCheckpoint>,
+        waypoint_index: u8,
+        approve: bool,
+    ) -> Result<()> {
+        instructions::waypoint::review_checkpoint(ctx, waypoint_index, approve)
+    }
+
+    /// Commit to a quiz answer
+    pub fn commit_quiz_answer(
+        ctx: Context<CommitQuizAnswer>,
+        location: String,
+        quest_index: u8,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::quiz::commit_quiz_answer(ctx, location, quest_index, commitment)
+    }
+
+    /// Withdraw a quiz commitment so a new answer can be committed
+    pub fn cancel_quiz_commitment(ctx: Context<CancelQuizCommitment>) -> Result<()> {
+        instructions::quiz::cancel_quiz_commitment(ctx)
+    }
+
+    /// Reveal a committed quiz answer and settle it on-chain
+    pub fn reveal_quiz_answer(
+        ctx: Context<RevealQuizAnswer>,
+        location: String,
+        quest_index: u8,
+        repeat_epoch: u32,
+        answer: String,
+        nonce: [u8; 32],
+    ) -> Result<()> {
+        instructions::quiz::reveal_quiz_answer(
+            ctx,
+            location,
+            quest_index,
+            repeat_epoch,
+            answer,
+            nonce,
+        )
+    }
+
+    /// Create a party led by the caller
+    pub fn create_party(ctx: Context<CreateParty>, max_size: u8) -> Result<()> {
+        instructions::party::create_party(ctx, max_size)
+    }
+
+    /// Add a member to the leader's party
+    pub fn add_party_member(ctx: Context<AddPartyMember>) -> Result<()> {
+        instructions::party::add_party_member(ctx)
+    }
+
+    /// Remove a member from a party
+    pub fn remove_party_member(ctx: Context<RemovePartyMember>, member: Pubkey) -> Result<()> {
+        instructions::party::remove_party_member(ctx, member)
+    }
+
+    /// Disband a party
+    pub fn disband_party(ctx: Context<DisbandParty>) -> Result<()> {
+        instructions::party::disband_party(ctx)
+    }
+
+    /// Create the caller's completion bitmap for a location
+    pub fn initialize_completions(ctx: Context<InitializeCompletions>, location: String) -> Result<()> {
+        instructions::party::initialize_completions(ctx, location)
+    }
+
+    /// Migrate the registry to the current account layout
+    pub fn migrate_registry(ctx: Context<MigrateRegistry>) -> Result<()> {
+        instructions::migrate::migrate_registry(ctx)
+    }
+
+    /// Migrate a location's quests to the current account layout
+    pub fn migrate_location_quests(
+        ctx: Context<MigrateLocationQuests>,
+        location: String,
+    ) -> Result<()> {
+        instructions::migrate::migrate_location_quests(ctx, location)
+    }
+
+    /// Migrate a user profile to the current account layout
+    pub fn migrate_user_profile(ctx: Context<MigrateUserProfile>) -> Result<()> {
+        instructions::migrate::migrate_user_profile(ctx)
+    }
+
+    /// Migrate a submission to the current account layout and address
+    pub fn migrate_submission(
+        ctx: Context<MigrateSubmission>,
+        location: String,
+        quest_index: u8,
     ) -> Result<()> {
-        instructions::reject_submission::reject_submission(ctx, reason)
+        instructions::migrate::migrate_submission(ctx, location, quest_index)
     }
 }
 
@@ -199,16 +494,117 @@ pub struct DeleteQuest<'info> {
 }
 
 #[derive(Accounts)]
+pub struct UpdateRegistryConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"quest_registry"],
+        bump,
+        has_one = authority @ XploraError::Unauthorized
+    )]
+    pub registry: Account<'info, QuestRegistry>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSponsorPolicy<'info> {
+    #[account(
+        seeds = [b"quest_registry"],
+        bump,
+        has_one = authority @ XploraError::Unauthorized
+    )]
+    pub registry: Account<'info, QuestRegistry>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SponsorPolicy::space(),
+        seeds = [b"sponsor_policy"],
+        bump
+    )]
+    pub sponsor_policy: Account<'info, SponsorPolicy>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(username: String)]
 pub struct InitializeUserProfile<'info> {
     #[account(
         init,
-        payer = user,
+        payer = fee_payer,
         space = UserProfile::space(),
         seeds = [b"user_profile", user.key().as_ref()],
         bump
     )]
     pub user_profile: Account<'info, UserProfile>,
     
+    #[account(
+        init,
+        payer = fee_payer,
+        space = UsernameRecord::space(),
+        seeds = [b"username", normalize_username(&username).as_bytes()],
+        bump
+    )]
+    pub username_record: Account<'info, UsernameRecord>,
+    
+    pub user: Signer<'info>,
+    
+    /// Pays rent and fees; either the user or an allowed relayer
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+    
+    /// Required when the fee payer is not the user
+    #[account(seeds = [b"sponsor_policy"], bump = sponsor_policy.bump)]
+    pub sponsor_policy: Option<Account<'info, SponsorPolicy>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateUserProfile<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(username: String)]
+pub struct ChangeUsername<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    /// Absent when the profile has no handle yet (auto-created profiles);
+    /// closed unless the new username normalizes to the same handle
+    #[account(
+        mut,
+        seeds = [b"username", normalize_username(&user_profile.username).as_bytes()],
+        bump = old_username_record.bump,
+        constraint = old_username_record.owner == user.key() @ XploraError::Unauthorized
+    )]
+    pub old_username_record: Option<Account<'info, UsernameRecord>>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UsernameRecord::space(),
+        seeds = [b"username", normalize_username(&username).as_bytes()],
+        bump
+    )]
+    pub new_username_record: Account<'info, UsernameRecord>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
     
@@ -216,37 +612,128 @@ pub struct InitializeUserProfile<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(location: String, quest_index: u8)]
-pub struct SubmitQuestCompletion<'info> {
+#[instruction(session_key: Pubkey)]
+pub struct CreateSessionKey<'info> {
     #[account(
         init,
-        payer = user,
+        payer = fee_payer,
+        space = SessionKey::space(),
+        seeds = [b"session_key", owner.key().as_ref(), session_key.as_ref()],
+        bump
+    )]
+    pub session: Account<'info, SessionKey>,
+    
+    pub owner: Signer<'info>,
+    
+    /// Pays rent and fees; either the owner or an allowed relayer
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+    
+    /// Required when the fee payer is not the owner
+    #[account(seeds = [b"sponsor_policy"], bump = sponsor_policy.bump)]
+    pub sponsor_policy: Option<Account<'info, SponsorPolicy>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeSessionKey<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"session_key", owner.key().as_ref(), session.session_key.as_ref()],
+        bump = session.bump
+    )]
+    pub session: Account<'info, SessionKey>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(location: String, quest_index: u8, repeat_epoch: u32)]
+pub struct SubmitQuestCompletion<'info> {
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
         space = QuestSubmission::space(),
         seeds = [
             b"submission",
             user.key().as_ref(),
             location.as_bytes(),
-            &[quest_index]
+            &[quest_index],
+            &repeat_epoch.to_le_bytes()
         ],
         bump
     )]
-    pub submission: Account<'info, QuestSubmission>,
+    pub submission: Box<Account<'info, QuestSubmission>>,
     
     #[account(
         seeds = [b"location_quests", location.as_bytes()],
         bump
     )]
-    pub location_quests: Account<'info, LocationQuests>,
+    pub location_quests: Box<Account<'info, LocationQuests>>,
+    
+    #[account(seeds = [b"quest_registry"], bump)]
+    pub registry: Box<Account<'info, QuestRegistry>>,
     
     #[account(
-        mut,
+        init_if_needed,
+        payer = fee_payer,
+        space = UserProfile::space(),
         seeds = [b"user_profile", user.key().as_ref()],
         bump
     )]
-    pub user_profile: Account<'info, UserProfile>,
+    pub user_profile: Box<Account<'info, UserProfile>>,
+    
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        space = QuestCompletions::LEN,
+        seeds = [b"completions", user.key().as_ref(), location_quests.key().as_ref()],
+        bump
+    )]
+    pub completions: Box<Account<'info, QuestCompletions>>,
+    
+    /// CHECK: Profile owner; authorized by `signer` being the user or one of its session keys
+    pub user: UncheckedAccount<'info>,
     
+    /// The user itself or a session key acting for the user; pays the deposit
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub signer: Signer<'info>,
+    
+    /// Required when `signer` is a session key
+    #[account(
+        seeds = [b"session_key", user.key().as_ref(), signer.key().as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+    
+    /// Check-in progress, required for trail and scavenger quests
+    #[account(
+        seeds = [b"progress", user.key().as_ref(), location_quests.key().as_ref(), &[quest_index]],
+        bump = progress.bump
+    )]
+    pub progress: Option<Account<'info, QuestProgress>>,
+    
+    /// The user's party, when submitting on behalf of the whole party
+    #[account(
+        seeds = [b"party", user.key().as_ref()],
+        bump = party.bump
+    )]
+    pub party: Option<Box<Account<'info, Party>>>,
+    
+    /// Pays rent and fees; either the signer or an allowed relayer
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+    
+    /// Required when the fee payer is not the signer
+    #[account(seeds = [b"sponsor_policy"], bump = sponsor_policy.bump)]
+    pub sponsor_policy: Option<Account<'info, SponsorPolicy>>,
+    
+    /// CHECK: Instructions sysvar, used to read a preceding location oracle attestation
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
     
     pub system_program: Program<'info, System>,
 }
@@ -259,13 +746,15 @@ pub struct ApproveSubmission<'info> {
             b"submission",
             submission.user.as_ref(),
             submission.location.as_bytes(),
-            &[submission.quest_index]
+            &[submission.quest_index],
+            &submission.repeat_epoch.to_le_bytes()
         ],
         bump = submission.bump
     )]
     pub submission: Account<'info, QuestSubmission>,
     
     #[account(
+        mut,
         seeds = [b"location_quests", submission.location.as_bytes()],
         bump
     )]
@@ -278,6 +767,13 @@ pub struct ApproveSubmission<'info> {
     )]
     pub user_profile: Account<'info, UserProfile>,
     
+    #[account(
+        mut,
+        seeds = [b"completions", submission.user.as_ref(), location_quests.key().as_ref()],
+        bump = completions.bump
+    )]
+    pub completions: Box<Account<'info, QuestCompletions>>,
+    
     #[account(
         mut,
         seeds = [b"quest_registry"],
@@ -286,13 +782,88 @@ pub struct ApproveSubmission<'info> {
     )]
     pub registry: Account<'info, QuestRegistry>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"validator", validator.key().as_ref()],
+        bump = validator_profile.bump,
+        constraint = validator_profile.active @ XploraError::ValidatorNotActive,
+        constraint = validator_profile.is_bonded(registry.config.min_validator_stake)
+            @ XploraError::InsufficientStake
+    )]
+    pub validator_profile: Account<'info, ValidatorProfile>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_vault"],
+        bump = reward_vault.bump
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+    
+    /// CHECK: Submission owner; receives the refunded deposit
+    #[account(mut, address = submission.user @ XploraError::Unauthorized)]
+    pub user: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = validator.key() != submission.user @ XploraError::SelfReview
+    )]
     pub validator: Signer<'info>,
     
     /// CHECK: This is the authority from registry
     pub authority: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct ApproveWithAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"submission",
+            submission.user.as_ref(),
+            submission.location.as_bytes(),
+            &[submission.quest_index],
+            &submission.repeat_epoch.to_le_bytes()
+        ],
+        bump = submission.bump
+    )]
+    pub submission: Box<Account<'info, QuestSubmission>>,
+    
+    #[account(
+        mut,
+        seeds = [b"location_quests", submission.location.as_bytes()],
+        bump
+    )]
+    pub location_quests: Box<Account<'info, LocationQuests>>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_profile", submission.user.as_ref()],
+        bump
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+    
+    #[account(
+        mut,
+        seeds = [b"completions", submission.user.as_ref(), location_quests.key().as_ref()],
+        bump = completions.bump
+    )]
+    pub completions: Box<Account<'info, QuestCompletions>>,
+    
+    #[account(seeds = [b"quest_registry"], bump)]
+    pub registry: Box<Account<'info, QuestRegistry>>,
+    
+    /// CHECK: Submission owner; receives the refunded deposit
+    #[account(mut, address = submission.user @ XploraError::Unauthorized)]
+    pub user: UncheckedAccount<'info>,
+    
+    /// Any relayer; custody of the AI validator key stays off-chain
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, used to read the AI validator's Ed25519 signature
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
 #[derive(Accounts)]
 pub struct RejectSubmission<'info> {
     #[account(
@@ -301,12 +872,26 @@ pub struct RejectSubmission<'info> {
             b"submission",
             submission.user.as_ref(),
             submission.location.as_bytes(),
-            &[submission.quest_index]
+            &[submission.quest_index],
+            &submission.repeat_epoch.to_le_bytes()
         ],
         bump = submission.bump
     )]
     pub submission: Account<'info, QuestSubmission>,
     
+    #[account(
+        seeds = [b"location_quests", submission.location.as_bytes()],
+        bump
+    )]
+    pub location_quests: Account<'info, LocationQuests>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_profile", submission.user.as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
     #[account(
         mut,
         seeds = [b"quest_registry"],
@@ -315,9 +900,1024 @@ pub struct RejectSubmission<'info> {
     )]
     pub registry: Account<'info, QuestRegistry>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"validator", validator.key().as_ref()],
+        bump = validator_profile.bump,
+        constraint = validator_profile.active @ XploraError::ValidatorNotActive,
+        constraint = validator_profile.is_bonded(registry.config.min_validator_stake)
+            @ XploraError::InsufficientStake
+    )]
+    pub validator_profile: Account<'info, ValidatorProfile>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_vault"],
+        bump = reward_vault.bump
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+    
+    #[account(
+        mut,
+        constraint = validator.key() != submission.user @ XploraError::SelfReview
+    )]
     pub validator: Signer<'info>,
     
     /// CHECK: This is the authority from registry
     pub authority: AccountInfo<'info>,
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct AppealSubmission<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"submission",
+            user.key().as_ref(),
+            submission.location.as_bytes(),
+            &[submission.quest_index],
+            &submission.repeat_epoch.to_le_bytes()
+        ],
+        bump = submission.bump
+    )]
+    pub submission: Box<Account<'info, QuestSubmission>>,
+    
+    #[account(seeds = [b"quest_registry"], bump)]
+    pub registry: Box<Account<'info, QuestRegistry>>,
+    
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveAppeal<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"submission",
+            submission.user.as_ref(),
+            submission.location.as_bytes(),
+            &[submission.quest_index],
+            &submission.repeat_epoch.to_le_bytes()
+        ],
+        bump = submission.bump
+    )]
+    pub submission: Box<Account<'info, QuestSubmission>>,
+    
+    #[account(
+        mut,
+        seeds = [b"location_quests", submission.location.as_bytes()],
+        bump
+    )]
+    pub location_quests: Box<Account<'info, LocationQuests>>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_profile", submission.user.as_ref()],
+        bump
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+    
+    #[account(
+        mut,
+        seeds = [b"completions", submission.user.as_ref(), location_quests.key().as_ref()],
+        bump = completions.bump
+    )]
+    pub completions: Box<Account<'info, QuestCompletions>>,
+    
+    #[account(
+        seeds = [b"quest_registry"],
+        bump,
+        constraint = reviewer.key() == registry.authority
+            || reviewer.key() == registry.config.appeals_reviewer @ XploraError::Unauthorized
+    )]
+    pub registry: Box<Account<'info, QuestRegistry>>,
+    
+    /// CHECK: Submission owner; receives the refunded deposit
+    #[account(mut, address = submission.user @ XploraError::Unauthorized)]
+    pub user: UncheckedAccount<'info>,
+    
+    pub reviewer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(validator: Pubkey)]
+pub struct RegisterValidator<'info> {
+    #[account(
+        seeds = [b"quest_registry"],
+        bump,
+        has_one = authority @ XploraError::Unauthorized
+    )]
+    pub registry: Account<'info, QuestRegistry>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = ValidatorProfile::space(),
+        seeds = [b"validator", validator.as_ref()],
+        bump
+    )]
+    pub validator_profile: Account<'info, ValidatorProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetValidatorActive<'info> {
+    #[account(
+        seeds = [b"quest_registry"],
+        bump,
+        has_one = authority @ XploraError::Unauthorized
+    )]
+    pub registry: Account<'info, QuestRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"validator", validator_profile.validator.as_ref()],
+        bump = validator_profile.bump
+    )]
+    pub validator_profile: Account<'info, ValidatorProfile>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"submission",
+            submission.user.as_ref(),
+            submission.location.as_bytes(),
+            &[submission.quest_index],
+            &submission.repeat_epoch.to_le_bytes()
+        ],
+        bump = submission.bump
+    )]
+    pub submission: Box<Account<'info, QuestSubmission>>,
+    
+    #[account(
+        mut,
+        seeds = [b"location_quests", submission.location.as_bytes()],
+        bump
+    )]
+    pub location_quests: Box<Account<'info, LocationQuests>>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_profile", submission.user.as_ref()],
+        bump
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+    
+    #[account(
+        mut,
+        seeds = [b"completions", submission.user.as_ref(), location_quests.key().as_ref()],
+        bump = completions.bump
+    )]
+    pub completions: Box<Account<'info, QuestCompletions>>,
+    
+    #[account(
+        seeds = [b"quest_registry"],
+        bump
+    )]
+    pub registry: Box<Account<'info, QuestRegistry>>,
+    
+    #[account(
+        mut,
+        seeds = [b"validator", validator.key().as_ref()],
+        bump = validator_profile.bump,
+        constraint = validator_profile.active @ XploraError::ValidatorNotActive,
+        constraint = validator_profile.is_bonded(registry.config.min_validator_stake)
+            @ XploraError::InsufficientStake
+    )]
+    pub validator_profile: Box<Account<'info, ValidatorProfile>>,
+    
+    #[account(
+        init_if_needed,
+        payer = validator,
+        space = SubmissionVote::space(),
+        seeds = [b"vote", submission.key().as_ref(), validator.key().as_ref()],
+        bump
+    )]
+    pub vote: Box<Account<'info, SubmissionVote>>,
+    
+    /// CHECK: Submission owner; receives the refunded deposit
+    #[account(mut, address = submission.user @ XploraError::Unauthorized)]
+    pub user: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = validator.key() != submission.user @ XploraError::SelfReview
+    )]
+    pub validator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVoteReward<'info> {
+    #[account(
+        seeds = [
+            b"submission",
+            submission.user.as_ref(),
+            submission.location.as_bytes(),
+            &[submission.quest_index],
+            &submission.repeat_epoch.to_le_bytes()
+        ],
+        bump = submission.bump
+    )]
+    pub submission: Box<Account<'info, QuestSubmission>>,
+    
+    #[account(
+        mut,
+        seeds = [b"vote", submission.key().as_ref(), validator.key().as_ref()],
+        bump = vote.bump
+    )]
+    pub vote: Box<Account<'info, SubmissionVote>>,
+    
+    #[account(
+        mut,
+        seeds = [b"validator", validator.key().as_ref()],
+        bump = validator_profile.bump
+    )]
+    pub validator_profile: Box<Account<'info, ValidatorProfile>>,
+    
+    pub validator: Signer<'info>,
+}
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(
+        seeds = [b"quest_registry"],
+        bump,
+        has_one = authority @ XploraError::Unauthorized
+    )]
+    pub registry: Account<'info, QuestRegistry>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Treasury::LEN,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakeValidator<'info> {
+    #[account(
+        mut,
+        seeds = [b"validator", validator.key().as_ref()],
+        bump = validator_profile.bump
+    )]
+    pub validator_profile: Account<'info, ValidatorProfile>,
+    
+    #[account(mut)]
+    pub validator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [b"validator", validator.key().as_ref()],
+        bump = validator_profile.bump
+    )]
+    pub validator_profile: Account<'info, ValidatorProfile>,
+    
+    pub validator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(
+        seeds = [b"quest_registry"],
+        bump
+    )]
+    pub registry: Account<'info, QuestRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"validator", validator.key().as_ref()],
+        bump = validator_profile.bump
+    )]
+    pub validator_profile: Account<'info, ValidatorProfile>,
+    
+    #[account(mut)]
+    pub validator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ChallengeReview<'info> {
+    #[account(
+        seeds = [
+            b"submission",
+            submission.user.as_ref(),
+            submission.location.as_bytes(),
+            &[submission.quest_index],
+            &submission.repeat_epoch.to_le_bytes()
+        ],
+        bump = submission.bump
+    )]
+    pub submission: Box<Account<'info, QuestSubmission>>,
+    
+    #[account(
+        init,
+        payer = challenger,
+        space = ReviewChallenge::space(),
+        seeds = [b"challenge", submission.key().as_ref(), challenger.key().as_ref()],
+        bump
+    )]
+    pub challenge: Box<Account<'info, ReviewChallenge>>,
+    
+    #[account(
+        mut,
+        seeds = [b"validator", validator_profile.validator.as_ref()],
+        bump = validator_profile.bump,
+        constraint = submission.validator == Some(validator_profile.validator)
+            @ XploraError::ChallengeNotAllowed
+    )]
+    pub validator_profile: Box<Account<'info, ValidatorProfile>>,
+    
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SlashValidator<'info> {
+    #[account(
+        seeds = [b"quest_registry"],
+        bump,
+        has_one = authority @ XploraError::Unauthorized
+    )]
+    pub registry: Account<'info, QuestRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge.submission.as_ref(), challenge.challenger.as_ref()],
+        bump = challenge.bump,
+        constraint = challenge.status == ChallengeStatus::Open @ XploraError::ChallengeNotOpen
+    )]
+    pub challenge: Account<'info, ReviewChallenge>,
+    
+    #[account(
+        mut,
+        seeds = [b"validator", challenge.validator.as_ref()],
+        bump = validator_profile.bump
+    )]
+    pub validator_profile: Account<'info, ValidatorProfile>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// CHECK: Receives the returned bond; checked against the challenge
+    #[account(mut, address = challenge.challenger @ XploraError::Unauthorized)]
+    pub challenger: AccountInfo<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DismissChallenge<'info> {
+    #[account(
+        seeds = [b"quest_registry"],
+        bump,
+        has_one = authority @ XploraError::Unauthorized
+    )]
+    pub registry: Account<'info, QuestRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"challenge", challenge.submission.as_ref(), challenge.challenger.as_ref()],
+        bump = challenge.bump,
+        constraint = challenge.status == ChallengeStatus::Open @ XploraError::ChallengeNotOpen
+    )]
+    pub challenge: Account<'info, ReviewChallenge>,
+    
+    #[account(
+        mut,
+        seeds = [b"validator", challenge.validator.as_ref()],
+        bump = validator_profile.bump
+    )]
+    pub validator_profile: Account<'info, ValidatorProfile>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRewardVault<'info> {
+    #[account(
+        seeds = [b"quest_registry"],
+        bump,
+        has_one = authority @ XploraError::Unauthorized
+    )]
+    pub registry: Account<'info, QuestRegistry>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = RewardVault::LEN,
+        seeds = [b"reward_vault"],
+        bump
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundRewardVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"reward_vault"],
+        bump = reward_vault.bump
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+    
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireSubmission<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"submission",
+            submission.user.as_ref(),
+            submission.location.as_bytes(),
+            &[submission.quest_index],
+            &submission.repeat_epoch.to_le_bytes()
+        ],
+        bump = submission.bump
+    )]
+    pub submission: Box<Account<'info, QuestSubmission>>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_profile", submission.user.as_ref()],
+        bump
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+    
+    #[account(seeds = [b"quest_registry"], bump)]
+    pub registry: Box<Account<'info, QuestRegistry>>,
+    
+    /// CHECK: Submission owner; receives the refunded deposit
+    #[account(mut, address = submission.user @ XploraError::Unauthorized)]
+    pub user: UncheckedAccount<'info>,
+    
+    /// Anyone may crank expiry
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"submission",
+            submission.user.as_ref(),
+            submission.location.as_bytes(),
+            &[submission.quest_index],
+            &submission.repeat_epoch.to_le_bytes()
+        ],
+        bump = submission.bump
+    )]
+    pub submission: Box<Account<'info, QuestSubmission>>,
+    
+    #[account(seeds = [b"quest_registry"], bump)]
+    pub registry: Box<Account<'info, QuestRegistry>>,
+    
+    /// CHECK: Submission owner; receives the refunded deposit
+    #[account(mut, address = submission.user @ XploraError::Unauthorized)]
+    pub user: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// Anyone may crank settlement
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetQuestStatus<'info> {
+    #[account(constraint = location_quests.initialized @ XploraError::NotInitialized)]
+    pub location_quests: Account<'info, LocationQuests>,
+}
+
+#[derive(Accounts)]
+#[instruction(location: String, quest_index: u8)]
+pub struct VisitWaypoint<'info> {
+    #[account(
+        seeds = [b"location_quests", location.as_bytes()],
+        bump
+    )]
+    pub location_quests: Box<Account<'info, LocationQuests>>,
+    
+    #[account(seeds = [b"quest_registry"], bump)]
+    pub registry: Box<Account<'info, QuestRegistry>>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = QuestProgress::LEN,
+        seeds = [b"progress", user.key().as_ref(), location_quests.key().as_ref(), &[quest_index]],
+        bump
+    )]
+    pub progress: Box<Account<'info, QuestProgress>>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserProfile::space(),
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, used to read a preceding location oracle attestation
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct JoinSubmission<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"submission",
+            submission.user.as_ref(),
+            submission.location.as_bytes(),
+            &[submission.quest_index],
+            &submission.repeat_epoch.to_le_bytes()
+        ],
+        bump = submission.bump
+    )]
+    pub submission: Box<Account<'info, QuestSubmission>>,
+    
+    #[account(
+        seeds = [b"location_quests", submission.location.as_bytes()],
+        bump
+    )]
+    pub location_quests: Box<Account<'info, LocationQuests>>,
+    
+    #[account(seeds = [b"quest_registry"], bump)]
+    pub registry: Box<Account<'info, QuestRegistry>>,
+    
+    #[account(
+        init_if_needed,
+        payer = participant,
+        space = UserProfile::space(),
+        seeds = [b"user_profile", participant.key().as_ref()],
+        bump
+    )]
+    pub participant_profile: Box<Account<'info, UserProfile>>,
+    
+    #[account(
+        init_if_needed,
+        payer = participant,
+        space = QuestCompletions::LEN,
+        seeds = [b"completions", participant.key().as_ref(), location_quests.key().as_ref()],
+        bump
+    )]
+    pub participant_completions: Box<Account<'info, QuestCompletions>>,
+    
+    #[account(mut)]
+    pub participant: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, used to read a preceding location oracle attestation
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReviewCheckpoint<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"progress",
+            progress.user.as_ref(),
+            progress.location_quests.as_ref(),
+            &[progress.quest_index]
+        ],
+        bump = progress.bump
+    )]
+    pub progress: Box<Account<'info, QuestProgress>>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_profile", progress.user.as_ref()],
+        bump
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+    
+    #[account(address = progress.location_quests)]
+    pub location_quests: Box<Account<'info, LocationQuests>>,
+    
+    #[account(seeds = [b"quest_registry"], bump)]
+    pub registry: Box<Account<'info, QuestRegistry>>,
+    
+    #[account(
+        seeds = [b"validator", validator.key().as_ref()],
+        bump = validator_profile.bump,
+        constraint = validator_profile.active @ XploraError::ValidatorNotActive,
+        constraint = validator_profile.is_bonded(registry.config.min_validator_stake)
+            @ XploraError::InsufficientStake
+    )]
+    pub validator_profile: Box<Account<'info, ValidatorProfile>>,
+    
+    #[account(constraint = validator.key() != progress.user @ XploraError::SelfReview)]
+    pub validator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(location: String, quest_index: u8)]
+pub struct CommitQuizAnswer<'info> {
+    #[account(
+        seeds = [b"location_quests", location.as_bytes()],
+        bump
+    )]
+    pub location_quests: Box<Account<'info, LocationQuests>>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = QuizCommitment::LEN,
+        seeds = [b"quiz_commit", user.key().as_ref(), location_quests.key().as_ref(), &[quest_index]],
+        bump
+    )]
+    pub commitment: Box<Account<'info, QuizCommitment>>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelQuizCommitment<'info> {
+    #[account(
+        mut,
+        close = user,
+        seeds = [
+            b"quiz_commit",
+            user.key().as_ref(),
+            commitment.location_quests.as_ref(),
+            &[commitment.quest_index]
+        ],
+        bump = commitment.bump
+    )]
+    pub commitment: Box<Account<'info, QuizCommitment>>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(location: String, quest_index: u8, repeat_epoch: u32)]
+pub struct RevealQuizAnswer<'info> {
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        space = QuestSubmission::space(),
+        seeds = [
+            b"submission",
+            user.key().as_ref(),
+            location.as_bytes(),
+            &[quest_index],
+            &repeat_epoch.to_le_bytes()
+        ],
+        bump
+    )]
+    pub submission: Box<Account<'info, QuestSubmission>>,
+    
+    #[account(
+        mut,
+        seeds = [b"location_quests", location.as_bytes()],
+        bump
+    )]
+    pub location_quests: Box<Account<'info, LocationQuests>>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Box<Account<'info, UserProfile>>,
+    
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        space = QuestCompletions::LEN,
+        seeds = [b"completions", user.key().as_ref(), location_quests.key().as_ref()],
+        bump
+    )]
+    pub completions: Box<Account<'info, QuestCompletions>>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"quiz_commit", user.key().as_ref(), location_quests.key().as_ref(), &[quest_index]],
+        bump = commitment.bump
+    )]
+    pub commitment: Box<Account<'info, QuizCommitment>>,
+    
+    #[account(seeds = [b"quest_registry"], bump)]
+    pub registry: Box<Account<'info, QuestRegistry>>,
+    
+    /// Pays the deposit
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Pays rent and fees; either the user or an allowed relayer
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+    
+    /// Required when the fee payer is not the user
+    #[account(seeds = [b"sponsor_policy"], bump = sponsor_policy.bump)]
+    pub sponsor_policy: Option<Account<'info, SponsorPolicy>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateParty<'info> {
+    #[account(
+        init,
+        payer = leader,
+        space = Party::LEN,
+        seeds = [b"party", leader.key().as_ref()],
+        bump
+    )]
+    pub party: Box<Account<'info, Party>>,
+    
+    #[account(
+        init,
+        payer = leader,
+        space = PartyMembership::LEN,
+        seeds = [b"party_membership", leader.key().as_ref()],
+        bump
+    )]
+    pub leader_membership: Box<Account<'info, PartyMembership>>,
+    
+    #[account(
+        seeds = [b"user_profile", leader.key().as_ref()],
+        bump
+    )]
+    pub leader_profile: Box<Account<'info, UserProfile>>,
+    
+    #[account(mut)]
+    pub leader: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddPartyMember<'info> {
+    #[account(
+        mut,
+        seeds = [b"party", leader.key().as_ref()],
+        bump = party.bump
+    )]
+    pub party: Box<Account<'info, Party>>,
+    
+    /// Rewards are credited to this profile, so it must exist
+    #[account(
+        seeds = [b"user_profile", member.key().as_ref()],
+        bump
+    )]
+    pub member_profile: Box<Account<'info, UserProfile>>,
+    
+    /// Fails to initialize if the member already belongs to a party
+    #[account(
+        init,
+        payer = member,
+        space = PartyMembership::LEN,
+        seeds = [b"party_membership", member.key().as_ref()],
+        bump
+    )]
+    pub member_membership: Box<Account<'info, PartyMembership>>,
+    
+    pub leader: Signer<'info>,
+    
+    #[account(mut)]
+    pub member: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct RemovePartyMember<'info> {
+    #[account(
+        mut,
+        seeds = [b"party", party.leader.as_ref()],
+        bump = party.bump
+    )]
+    pub party: Box<Account<'info, Party>>,
+    
+    #[account(
+        mut,
+        close = member_wallet,
+        seeds = [b"party_membership", member.as_ref()],
+        bump = membership.bump,
+        constraint = membership.party == party.key() @ XploraError::NotPartyMember
+    )]
+    pub membership: Box<Account<'info, PartyMembership>>,
+    
+    /// CHECK: The member leaving; receives the membership rent
+    #[account(mut, address = member)]
+    pub member_wallet: UncheckedAccount<'info>,
+    
+    /// The leader or the member leaving
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DisbandParty<'info> {
+    #[account(
+        mut,
+        close = leader,
+        seeds = [b"party", leader.key().as_ref()],
+        bump = party.bump
+    )]
+    pub party: Box<Account<'info, Party>>,
+    
+    #[account(
+        mut,
+        close = leader,
+        seeds = [b"party_membership", leader.key().as_ref()],
+        bump = leader_membership.bump
+    )]
+    pub leader_membership: Box<Account<'info, PartyMembership>>,
+    
+    #[account(mut)]
+    pub leader: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(location: String)]
+pub struct InitializeCompletions<'info> {
+    #[account(
+        seeds = [b"location_quests", location.as_bytes()],
+        bump
+    )]
+    pub location_quests: Box<Account<'info, LocationQuests>>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = QuestCompletions::LEN,
+        seeds = [b"completions", user.key().as_ref(), location_quests.key().as_ref()],
+        bump
+    )]
+    pub completions: Box<Account<'info, QuestCompletions>>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateRegistry<'info> {
+    /// CHECK: Version 1 registry; authority is checked after resizing
+    #[account(mut, seeds = [b"quest_registry"], bump, owner = crate::ID)]
+    pub registry: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(location: String)]
+pub struct MigrateLocationQuests<'info> {
+    #[account(
+        seeds = [b"quest_registry"],
+        bump,
+        has_one = authority @ XploraError::Unauthorized
+    )]
+    pub registry: Account<'info, QuestRegistry>,
+    
+    /// CHECK: Version 1 location quests, decoded with the legacy layout
+    #[account(
+        mut,
+        seeds = [b"location_quests", location.as_bytes()],
+        bump,
+        owner = crate::ID
+    )]
+    pub location_quests: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateUserProfile<'info> {
+    /// CHECK: Version 1 profile, grown in place
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub user_profile: UncheckedAccount<'info>,
+    
+    /// CHECK: Profile owner; only used to derive the profile address
+    pub user: UncheckedAccount<'info>,
+    
+    /// Anyone may pay for the migration
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(location: String, quest_index: u8)]
+pub struct MigrateSubmission<'info> {
+    /// CHECK: Version 1 submission at its old address, decoded with the legacy layout
+    #[account(
+        mut,
+        seeds = [b"submission", user.key().as_ref(), location.as_bytes(), &[quest_index]],
+        bump,
+        owner = crate::ID
+    )]
+    pub legacy_submission: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = QuestSubmission::space(),
+        seeds = [
+            b"submission",
+            user.key().as_ref(),
+            location.as_bytes(),
+            &[quest_index],
+            &0u32.to_le_bytes()
+        ],
+        bump
+    )]
+    pub submission: Box<Account<'info, QuestSubmission>>,
+    
+    /// Must already be migrated, to resolve the quest's stable id
+    #[account(
+        seeds = [b"location_quests", location.as_bytes()],
+        bump
+    )]
+    pub location_quests: Box<Account<'info, LocationQuests>>,
+    
+    /// CHECK: Submitter; receives the old account's rent
+    #[account(mut)]
+    pub user: UncheckedAccount<'info>,
+    
+    /// Anyone may pay for the migration
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
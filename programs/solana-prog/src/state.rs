@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::errors::XploraError;
 
 /// Global registry that tracks all locations and manages authority
 #[account]
@@ -14,10 +15,93 @@ pub struct QuestRegistry {
     
     /// Reserved space for future fields
     pub reserved: [u8; 7],
+    
+    /// Authority-configurable program settings
+    pub config: RegistryConfig,
 }
 
 impl QuestRegistry {
-    pub const LEN: usize = 8 + 32 + 8 + 1 + 7; // discriminator + fields
+    pub const LEN: usize = 8 + 32 + 8 + 1 + 7 + RegistryConfig::LEN; // discriminator + fields
+}
+
+/// Program settings adjustable by the registry authority
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct RegistryConfig {
+    /// Max distance between the claimed position and the quest coordinates (0 = unchecked)
+    pub max_submission_distance_m: u32,
+    
+    /// Max accepted GPS accuracy radius
+    pub max_gps_accuracy_m: u32,
+    
+    /// Trusted location oracle signing Ed25519 location attestations (default = none)
+    pub location_oracle: Pubkey,
+    
+    /// AI validator key whose signed verdicts can approve submissions (default = none)
+    pub ai_validator: Pubkey,
+    
+    /// Minimum AI confidence (basis points) for automated approval
+    pub min_ai_confidence_bps: u16,
+    
+    /// Senior reviewer allowed to resolve appeals besides the authority (default = none)
+    pub appeals_reviewer: Pubkey,
+    
+    /// How long after rejection a user may appeal
+    pub appeal_window_seconds: i64,
+    
+    /// Minimum bonded stake (lamports) for a validator to review
+    pub min_validator_stake: u64,
+    
+    /// Delay between requesting an unstake and withdrawing it
+    pub unbonding_period_seconds: i64,
+    
+    /// Fee (lamports) paid from the reward vault per reviewed submission
+    pub review_fee_lamports: u64,
+    
+    /// Max paid reviews per validator per day; further reviews earn no fee
+    pub max_paid_reviews_per_day: u16,
+    
+    /// Time a submission may stay pending before anyone can expire it (0 = never)
+    pub review_sla_seconds: i64,
+    
+    /// Whether expiring a submission gives the user back the attempt
+    pub restore_attempt_on_expiry: bool,
+    
+    /// Refundable deposit (lamports) held in each submission PDA
+    pub submission_deposit_lamports: u64,
+    
+    /// Max submissions a user may have awaiting review (0 = unlimited)
+    pub max_pending_submissions: u16,
+    
+    /// Max submissions per user in any rolling 24h window
+    /// (0 = unlimited, at most `UserProfile::SUBMISSION_HISTORY_LEN`)
+    pub max_submissions_per_day: u16,
+}
+
+impl RegistryConfig {
+    pub const LEN: usize = 4 + 4 + 32 + 32 + 2 + 32 + 8 + 8 + 8 + 8 + 2 + 8 + 1 + 8 + 2 + 2;
+}
+
+impl Default for RegistryConfig {
+    fn default() -> Self {
+        Self {
+            max_submission_distance_m: constants::DEFAULT_MAX_SUBMISSION_DISTANCE_M,
+            max_gps_accuracy_m: constants::DEFAULT_MAX_GPS_ACCURACY_M,
+            location_oracle: Pubkey::default(),
+            ai_validator: Pubkey::default(),
+            min_ai_confidence_bps: constants::DEFAULT_MIN_AI_CONFIDENCE_BPS,
+            appeals_reviewer: Pubkey::default(),
+            appeal_window_seconds: constants::DEFAULT_APPEAL_WINDOW_SECONDS,
+            min_validator_stake: constants::DEFAULT_MIN_VALIDATOR_STAKE,
+            unbonding_period_seconds: constants::DEFAULT_UNBONDING_PERIOD_SECONDS,
+            review_fee_lamports: constants::DEFAULT_REVIEW_FEE_LAMPORTS,
+            max_paid_reviews_per_day: constants::DEFAULT_MAX_PAID_REVIEWS_PER_DAY,
+            review_sla_seconds: constants::DEFAULT_REVIEW_SLA_SECONDS,
+            restore_attempt_on_expiry: true,
+            submission_deposit_lamports: constants::DEFAULT_SUBMISSION_DEPOSIT_LAMPORTS,
+            max_pending_submissions: constants::DEFAULT_MAX_PENDING_SUBMISSIONS,
+            max_submissions_per_day: constants::DEFAULT_MAX_SUBMISSIONS_PER_DAY,
+        }
+    }
 }
 
 /// Per-location quest storage
@@ -40,11 +124,16 @@ pub struct LocationQuests {
     
     /// Reserved space for future fields
     pub reserved: [u8; 6],
+    
+    /// Id assigned to the next quest added (ids are never reused)
+    pub next_quest_id: u16,
 }
 
 impl LocationQuests {
     pub const MAX_QUESTS: usize = 10; // Reduced for memory constraints
     pub const MAX_LOCATION_LEN: usize = 64; // Reduced for reasonable sizing
+    /// Quest ids are tracked in a 256-bit completion bitmap
+    pub const MAX_QUEST_IDS: usize = 256;
     
     /// Calculate the maximum space needed for this account
     /// We allocate space for MAX_QUESTS to allow growing the Vec
@@ -55,7 +144,22 @@ impl LocationQuests {
         1 + // initialized bool
         8 + // created_at i64
         8 + // updated_at i64
-        6 // reserved
+        6 + // reserved
+        2 // next_quest_id u16
+    }
+    
+    /// Look a quest up by its stable id
+    pub fn quest_by_id(&self, id: u16) -> Result<&Quest> {
+        self.quests.iter()
+            .find(|quest| quest.id == id)
+            .ok_or_else(|| error!(XploraError::QuestNotFound))
+    }
+    
+    /// Look a quest up by its stable id for modification
+    pub fn quest_by_id_mut(&mut self, id: u16) -> Result<&mut Quest> {
+        self.quests.iter_mut()
+            .find(|quest| quest.id == id)
+            .ok_or_else(|| error!(XploraError::QuestNotFound))
     }
 }
 
@@ -94,6 +198,56 @@ pub struct Quest {
     
     /// Reserved space for future fields
     pub reserved: [u8; 4],
+    
+    /// Whether submissions need a location oracle attestation
+    pub requires_location_attestation: bool,
+    
+    /// Validator votes needed to resolve a submission (0 or 1 = single validator)
+    pub required_votes: u8,
+    
+    /// Stable quest id within its location (assigned on-chain)
+    pub id: u16,
+    
+    /// Quests that must be completed before this one can be submitted
+    pub prerequisites: Vec<QuestRef>,
+    
+    /// Bonus XP for finishing the chain this quest concludes
+    pub chain_bonus_xp: u32,
+    
+    /// Minimum user level to submit (0 = no requirement)
+    pub min_level: u16,
+    
+    /// Minimum rank tier to submit
+    pub min_rank_tier: Option<RankTier>,
+    
+    /// Max approved completions (0 = unlimited)
+    pub max_completions: u32,
+    
+    /// Approved completions so far (maintained on-chain)
+    pub completions: u32,
+    
+    /// First N approvals earn the early-bird bonus
+    pub early_bird_slots: u32,
+    
+    /// Early-bird token bonus in basis points of the base reward
+    pub early_bird_bonus_bps: u16,
+    
+    /// When submissions open (0 = immediately)
+    pub starts_at: i64,
+    
+    /// When submissions close (0 = never)
+    pub ends_at: i64,
+    
+    /// Whether the quest can be completed again after the cooldown
+    pub repeatable: bool,
+    
+    /// Length of the repeat windows; one submission per window. Windows are
+    /// fixed (aligned to multiples of the cooldown since the Unix epoch), not
+    /// measured from the user's last approval
+    pub cooldown_seconds: u32,
+    
+    /// XP and tokens awarded on repeat completions, in basis points of the normal reward
+    pub repeat_xp_bps: u16,
 }
 
 impl Quest {
@@ -101,12 +255,13 @@ impl Quest {
     pub const MAX_DESCRIPTION_LEN: usize = 128;  // Reduced
     pub const MAX_LANDMARK_LEN: usize = 64;  // Reduced
     pub const MAX_LANDMARK_NAME_LEN: usize = 32;  // Reduced
+    pub const MAX_PREREQUISITES: usize = 3;
     
     /// Calculate the maximum size of a Quest struct
     pub const fn max_size() -> usize {
         4 + Self::MAX_TITLE_LEN + // title
         4 + Self::MAX_DESCRIPTION_LEN + // description
-        1 + // quest_type enum
+        QuestType::max_size() + // quest_type enum
         1 + // difficulty enum
         2 + // time_to_live_hours u16
         4 + Self::MAX_LANDMARK_LEN + // verifiable_landmark
@@ -114,7 +269,185 @@ impl Quest {
         8 + // latitude f64
         8 + // longitude f64
         8 + // created_at i64
-        4 // reserved
+        4 + // reserved
+        1 + // requires_location_attestation bool
+        1 + // required_votes u8
+        2 + // id u16
+        4 + (Self::MAX_PREREQUISITES * QuestRef::LEN) + // prerequisites vec
+        4 + // chain_bonus_xp u32
+        2 + // min_level u16
+        1 + 1 + // min_rank_tier option
+        4 + // max_completions u32
+        4 + // completions u32
+        4 + // early_bird_slots u32
+        2 + // early_bird_bonus_bps u16
+        8 + // starts_at i64
+        8 + // ends_at i64
+        1 + // repeatable bool
+        4 + // cooldown_seconds u32
+        2 // repeat_xp_bps u16
+    }
+    
+    /// Whether every completion slot has been taken
+    pub fn is_full(&self) -> bool {
+        self.max_completions > 0 && self.completions >= self.max_completions
+    }
+    
+    /// Fixed cooldown window a submission made at `now` belongs to (always 0 for
+    /// one-shot quests). Two submissions in adjacent windows can be seconds apart.
+    pub fn repeat_epoch(&self, now: i64) -> u32 {
+        if self.repeatable && self.cooldown_seconds > 0 {
+            (now / self.cooldown_seconds as i64) as u32
+        } else {
+            0
+        }
+    }
+    
+    /// Schedule status of the quest at `now`
+    pub fn quest_status(&self, now: i64) -> QuestStatus {
+        if now < self.starts_at {
+            QuestStatus::Scheduled
+        } else if self.ends_at != 0 && now >= self.ends_at {
+            QuestStatus::Expired
+        } else {
+            QuestStatus::Active
+        }
+    }
+}
+
+/// Schedule status of a quest
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum QuestStatus {
+    /// Published but not yet open for submissions
+    Scheduled,
+    
+    /// Open for submissions
+    Active,
+    
+    /// Past its end time
+    Expired,
+}
+
+/// Reference to a quest by location account and stable id
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct QuestRef {
+    /// Location quests account holding the quest
+    pub location_quests: Pubkey,
+    
+    /// Stable quest id within that location
+    pub quest_id: u16,
+}
+
+impl QuestRef {
+    pub const LEN: usize = 32 + 2;
+}
+
+/// A travel group whose leader submits quests on behalf of every member
+#[account]
+pub struct Party {
+    /// Party leader; the only member who can submit for the party
+    pub leader: Pubkey,
+    
+    /// Members other than the leader
+    pub members: Vec<Pubkey>,
+    
+    /// Maximum party size, including the leader
+    pub max_size: u8,
+    
+    /// Creation timestamp
+    pub created_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Party {
+    pub const MAX_MEMBERS: usize = 8;
+    pub const LEN: usize = 8 + 32 + 4 + (Self::MAX_MEMBERS - 1) * 32 + 1 + 8 + 1;
+    
+    pub fn is_full(&self) -> bool {
+        self.members.len() + 1 >= self.max_size as usize
+    }
+}
+
+/// Records the single party a user belongs to, as leader or member
+#[account]
+pub struct PartyMembership {
+    /// User wallet
+    pub member: Pubkey,
+    
+    /// Party account
+    pub party: Pubkey,
+    
+    /// Join timestamp
+    pub joined_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PartyMembership {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+/// A user's hidden answer to a quiz quest, revealed in a later slot
+#[account]
+pub struct QuizCommitment {
+    /// User wallet
+    pub user: Pubkey,
+    
+    /// Location quests account holding the quiz
+    pub location_quests: Pubkey,
+    
+    /// Quest index
+    pub quest_index: u8,
+    
+    /// sha256(user || nonce || normalized answer)
+    pub commitment: [u8; 32],
+    
+    /// Slot of the commit; the reveal must land in a later slot
+    pub committed_slot: u64,
+    
+    /// PDA bump
+    pub bump: u8,
+    
+    /// Stable id of the quest
+    pub quest_id: u16,
+}
+
+impl QuizCommitment {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 32 + 8 + 1 + 2;
+}
+
+/// Per-user, per-location bitmap of approved quest ids
+#[account]
+pub struct QuestCompletions {
+    /// User wallet
+    pub user: Pubkey,
+    
+    /// Location quests account the bitmap refers to
+    pub location_quests: Pubkey,
+    
+    /// Bit `id` is set once quest `id` has been approved
+    pub completed: [u8; LocationQuests::MAX_QUEST_IDS / 8],
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl QuestCompletions {
+    pub const LEN: usize = 8 + 32 + 32 + LocationQuests::MAX_QUEST_IDS / 8 + 1;
+    
+    pub fn is_completed(&self, quest_id: u16) -> bool {
+        let id = quest_id as usize;
+        id < LocationQuests::MAX_QUEST_IDS && self.completed[id / 8] & (1 << (id % 8)) != 0
+    }
+    
+    pub fn mark_completed(&mut self, quest_id: u16) {
+        let id = quest_id as usize;
+        if id < LocationQuests::MAX_QUEST_IDS {
+            self.completed[id / 8] |= 1 << (id % 8);
+        }
     }
 }
 
@@ -129,6 +462,97 @@ pub enum QuestType {
     
     /// Challenge quests - completing tasks
     Challenge,
+    
+    /// Ordered route; every waypoint must be visited in order
+    Trail { waypoints: Vec<Waypoint> },
+    
+    /// Visit `required` of the listed landmarks, in any order
+    Scavenger { landmarks: Vec<Waypoint>, required: u8 },
+    
+    /// Knowledge check; `answer_hash` = sha256(salt || normalized answer)
+    Quiz { answer_hash: [u8; 32], salt: [u8; 16] },
+    
+    /// Must be co-submitted by `required_participants` users in total
+    Social { required_participants: u8 },
+}
+
+impl QuestType {
+    pub const MAX_WAYPOINTS: usize = 8;
+    
+    /// Largest serialized variant
+    pub const fn max_size() -> usize {
+        1 + // variant tag
+        4 + (Self::MAX_WAYPOINTS * Waypoint::LEN) + 1 // scavenger landmarks + required
+    }
+    
+    /// Waypoints or landmarks a user checks in at, if any
+    pub fn waypoints(&self) -> Option<&[Waypoint]> {
+        match self {
+            QuestType::Trail { waypoints } => Some(waypoints),
+            QuestType::Scavenger { landmarks, .. } => Some(landmarks),
+            _ => None,
+        }
+    }
+}
+
+/// A point on a trail or scavenger hunt, in microdegrees
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Waypoint {
+    pub latitude_e6: i32,
+    pub longitude_e6: i32,
+}
+
+impl Waypoint {
+    pub const LEN: usize = 4 + 4;
+}
+
+/// A user's check-ins on a trail or scavenger quest
+#[account]
+pub struct QuestProgress {
+    /// User wallet
+    pub user: Pubkey,
+    
+    /// Location quests account holding the quest
+    pub location_quests: Pubkey,
+    
+    /// Quest index
+    pub quest_index: u8,
+    
+    /// Bit `i` is set once waypoint `i` has been visited
+    pub visited: u8,
+    
+    /// Number of waypoints visited
+    pub visit_count: u8,
+    
+    /// Bit `i` is set once the check-in at waypoint `i` has been approved
+    pub approved: u8,
+    
+    /// Number of approved check-ins
+    pub approved_count: u8,
+    
+    /// XP earned from approved check-ins
+    pub xp_earned: u64,
+    
+    /// Last check-in timestamp
+    pub last_visit_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+    
+    /// Stable id of the quest
+    pub quest_id: u16,
+}
+
+impl QuestProgress {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 1 + 1 + 1 + 1 + 8 + 8 + 1 + 2;
+    
+    pub fn is_visited(&self, index: usize) -> bool {
+        self.visited & (1 << index) != 0
+    }
+    
+    pub fn is_approved(&self, index: usize) -> bool {
+        self.approved & (1 << index) != 0
+    }
 }
 
 /// Difficulty levels for quests
@@ -156,8 +580,8 @@ pub struct QuestSubmission {
     /// Quest index
     pub quest_index: u8,
     
-    /// IPFS hash of submitted photo
-    pub ipfs_hash: String,
+    /// Evidence attachments (photos, videos, audio, metadata)
+    pub evidence: Vec<Evidence>,
     
     /// Optional user description
     pub description: String,
@@ -182,18 +606,81 @@ pub struct QuestSubmission {
     
     /// PDA bump
     pub bump: u8,
+    
+    /// Cooldown window of a repeatable quest (0 for one-shot quests)
+    pub repeat_epoch: u32,
+    
+    /// User had already completed the quest when submitting
+    pub is_repeat: bool,
+    
+    /// Co-submitters of a social quest
+    pub participants: Vec<Pubkey>,
+    
+    /// Party members besides `user` sharing the rewards, snapshotted at submission
+    pub party_members: Vec<Pubkey>,
+    
+    /// GPS fix claimed by the user
+    pub location_proof: LocationProof,
+    
+    /// Computed distance from the quest coordinates
+    pub distance_m: u32,
+    
+    /// Whether the GPS fix was attested by the location oracle
+    pub location_attested: bool,
+    
+    /// Signed AI validator verdict, if approved automatically
+    pub ai_verdict: Option<AiVerdict>,
+    
+    /// Approve votes cast (consensus mode)
+    pub approve_votes: u8,
+    
+    /// Reject votes cast (consensus mode)
+    pub reject_votes: u8,
+    
+    /// Reject votes that also flagged the submission as fraudulent
+    pub fraud_votes: u8,
+    
+    /// Tokens set aside for validators who voted with the outcome
+    pub validator_reward_pool: u64,
+    
+    /// Reason given when rejected
+    pub rejection_reason: String,
+    
+    /// User's appeal statement
+    pub appeal_reason: String,
+    
+    /// Appeal timestamp (0 = never appealed)
+    pub appealed_at: i64,
+    
+    /// XP awarded if approved
+    pub xp_reward: u64,
+    
+    /// Anti-spam deposit held in this account until settled
+    pub deposit_lamports: u64,
+    
+    /// Rejected as fraudulent; the deposit is forfeited
+    pub flagged_fraud: bool,
+    
+    /// Stable id of the quest, unaffected by later quest deletions
+    pub quest_id: u16,
 }
 
 impl QuestSubmission {
-    pub const MAX_IPFS_HASH_LEN: usize = 64;
+    /// Longest accepted textual CID (base32 CIDv1 with a 512-bit digest)
+    pub const MAX_IPFS_HASH_LEN: usize = 128;
+    /// Longest binary CID: version + codec + multihash code + length + 64-byte digest
+    pub const MAX_CID_BYTES: usize = 72;
+    pub const MAX_EVIDENCE_ITEMS: usize = 4;
     pub const MAX_DESCRIPTION_LEN: usize = 200;
+    pub const MAX_REASON_LEN: usize = 200;
+    pub const MAX_PARTICIPANTS: usize = 4;
     
     pub fn space() -> usize {
         8 + // discriminator
         32 + // user pubkey
         4 + 64 + // location
         1 + // quest_index
-        4 + Self::MAX_IPFS_HASH_LEN + // ipfs_hash
+        4 + (Self::MAX_EVIDENCE_ITEMS * Evidence::max_size()) + // evidence vec
         4 + Self::MAX_DESCRIPTION_LEN + // description
         8 + // submitted_at
         1 + // status enum
@@ -201,16 +688,380 @@ impl QuestSubmission {
         1 + 8 + // validated_at option
         8 + // reward_amount
         1 + // attempt_number
-        1 // bump
+        1 + // bump
+        4 + // repeat_epoch
+        1 + // is_repeat
+        4 + (Self::MAX_PARTICIPANTS * 32) + // participants
+        4 + ((Party::MAX_MEMBERS - 1) * 32) + // party_members
+        LocationProof::LEN + // location_proof
+        4 + // distance_m
+        1 + // location_attested
+        1 + AiVerdict::max_size() + // ai_verdict option
+        1 + // approve_votes
+        1 + // reject_votes
+        1 + // fraud_votes
+        8 + // validator_reward_pool
+        4 + Self::MAX_REASON_LEN + // rejection_reason
+        4 + Self::MAX_REASON_LEN + // appeal_reason
+        8 + // appealed_at
+        8 + // xp_reward
+        8 + // deposit_lamports
+        1 + // flagged_fraud
+        2 // quest_id
+    }
+}
+
+/// GPS fix reported with a submission, in fixed-point microdegrees
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct LocationProof {
+    /// Latitude in microdegrees (degrees * 1e6)
+    pub latitude_e6: i32,
+    
+    /// Longitude in microdegrees (degrees * 1e6)
+    pub longitude_e6: i32,
+    
+    /// Reported accuracy radius in meters
+    pub accuracy_m: u32,
+    
+    /// When the fix was captured
+    pub captured_at: i64,
+}
+
+impl LocationProof {
+    pub const LEN: usize = 4 + 4 + 4 + 8;
+    
+    /// Domain separator for location oracle attestations
+    pub const ATTESTATION_DOMAIN: &'static [u8] = b"xplora:location:v1";
+    
+    /// Message the location oracle signs:
+    /// domain | user | location_quests | quest_index | latitude_e6 | longitude_e6 | captured_at
+    pub fn attestation_message(
+        &self,
+        user: &Pubkey,
+        location_quests: &Pubkey,
+        quest_index: u8,
+    ) -> Vec<u8> {
+        let mut message = Vec::with_capacity(Self::ATTESTATION_DOMAIN.len() + 32 + 32 + 1 + 16);
+        message.extend_from_slice(Self::ATTESTATION_DOMAIN);
+        message.extend_from_slice(user.as_ref());
+        message.extend_from_slice(location_quests.as_ref());
+        message.push(quest_index);
+        message.extend_from_slice(&self.latitude_e6.to_le_bytes());
+        message.extend_from_slice(&self.longitude_e6.to_le_bytes());
+        message.extend_from_slice(&self.captured_at.to_le_bytes());
+        message
     }
 }
 
+/// Verdict signed by the AI validator, kept on the submission for auditing
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct AiVerdict {
+    /// AI validator key that signed the verdict
+    pub signer: Pubkey,
+    
+    /// Model confidence in basis points (10_000 = 100%)
+    pub confidence_bps: u16,
+    
+    /// Model version that produced the verdict
+    pub model_version: String,
+    
+    /// When the verdict was signed
+    pub signed_at: i64,
+}
+
+impl AiVerdict {
+    pub const MAX_MODEL_VERSION_LEN: usize = 32;
+    
+    /// Domain separator for AI validator verdicts
+    pub const ATTESTATION_DOMAIN: &'static [u8] = b"xplora:verdict:v1";
+    
+    pub const fn max_size() -> usize {
+        32 + // signer
+        2 + // confidence_bps
+        4 + Self::MAX_MODEL_VERSION_LEN + // model_version
+        8 // signed_at
+    }
+    
+    /// Message the AI validator signs:
+    /// domain | submission | confidence_bps | signed_at | model_version
+    pub fn attestation_message(&self, submission: &Pubkey) -> Vec<u8> {
+        let mut message = Vec::with_capacity(
+            Self::ATTESTATION_DOMAIN.len() + 32 + 2 + 8 + self.model_version.len(),
+        );
+        message.extend_from_slice(Self::ATTESTATION_DOMAIN);
+        message.extend_from_slice(submission.as_ref());
+        message.extend_from_slice(&self.confidence_bps.to_le_bytes());
+        message.extend_from_slice(&self.signed_at.to_le_bytes());
+        message.extend_from_slice(self.model_version.as_bytes());
+        message
+    }
+}
+
+/// Evidence attachment stored on a submission
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Evidence {
+    /// Binary CID (see `utils::parse_cid`)
+    pub cid: Vec<u8>,
+    
+    /// Kind of media behind the CID
+    pub media_type: MediaType,
+    
+    /// SHA-256 of the original file, for integrity checks off-chain
+    pub content_hash: [u8; 32],
+}
+
+impl Evidence {
+    pub const fn max_size() -> usize {
+        4 + QuestSubmission::MAX_CID_BYTES + // cid
+        1 + // media_type enum
+        32 // content_hash
+    }
+}
+
+/// Evidence attachment as submitted by clients, with a textual CID
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct EvidenceAttachment {
+    /// IPFS CID (v0 or v1)
+    pub cid: String,
+    
+    /// Kind of media behind the CID
+    pub media_type: MediaType,
+    
+    /// SHA-256 of the original file
+    pub content_hash: [u8; 32],
+}
+
+/// Media types accepted as evidence
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum MediaType {
+    Image,
+    Video,
+    Audio,
+    /// JSON metadata (e.g. EXIF or sensor dumps)
+    Metadata,
+}
+
 /// Submission status enum
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 pub enum SubmissionStatus {
     Pending,
     Approved,
     Rejected,
+    /// Rejected, awaiting senior review
+    Appealed,
+    /// Not reviewed within the review SLA
+    Expired,
+}
+
+/// Registered validator allowed to vote on consensus submissions
+#[account]
+pub struct ValidatorProfile {
+    /// Validator wallet
+    pub validator: Pubkey,
+    
+    /// Whether the validator may currently vote
+    pub active: bool,
+    
+    /// Total votes cast
+    pub votes_cast: u32,
+    
+    /// Votes that matched the consensus outcome
+    pub agreeing_votes: u32,
+    
+    /// Total tokens earned from consensus reward pools
+    pub tokens_earned: u64,
+    
+    /// Bonded stake held in this account (lamports above rent)
+    pub staked_lamports: u64,
+    
+    /// Stake waiting out the unbonding period (still slashable)
+    pub unbonding_lamports: u64,
+    
+    /// When the current unbonding started
+    pub unbonding_started_at: i64,
+    
+    /// Total stake slashed
+    pub slashed_lamports: u64,
+    
+    /// Submissions approved or rejected directly by this validator
+    pub reviews_completed: u32,
+    
+    /// Total review fees received (lamports)
+    pub review_fees_earned: u64,
+    
+    /// Day index (unix days) of `paid_reviews_today`
+    pub fee_day: i64,
+    
+    /// Paid reviews on `fee_day`
+    pub paid_reviews_today: u16,
+    
+    /// Challenges against this validator's decisions not yet resolved
+    pub open_challenges: u16,
+    
+    /// Registration timestamp
+    pub registered_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ValidatorProfile {
+    pub fn space() -> usize {
+        8 + // discriminator
+        32 + // validator pubkey
+        1 + // active
+        4 + // votes_cast
+        4 + // agreeing_votes
+        8 + // tokens_earned
+        8 + // staked_lamports
+        8 + // unbonding_lamports
+        8 + // unbonding_started_at
+        8 + // slashed_lamports
+        4 + // reviews_completed
+        8 + // review_fees_earned
+        8 + // fee_day
+        2 + // paid_reviews_today
+        2 + // open_challenges
+        8 + // registered_at
+        1 // bump
+    }
+    
+    /// Whether the validator has at least `min_stake` bonded
+    pub fn is_bonded(&self, min_stake: u64) -> bool {
+        self.staked_lamports >= min_stake
+    }
+}
+
+/// Challenge against a validator's review decision
+#[account]
+pub struct ReviewChallenge {
+    /// Challenged submission
+    pub submission: Pubkey,
+    
+    /// Validator who made the decision
+    pub validator: Pubkey,
+    
+    /// Who raised the challenge
+    pub challenger: Pubkey,
+    
+    /// Why the decision is believed wrong
+    pub reason: String,
+    
+    /// Current status
+    pub status: ChallengeStatus,
+    
+    /// Lamports slashed if upheld
+    pub slashed_lamports: u64,
+    
+    /// Creation timestamp
+    pub created_at: i64,
+    
+    /// Resolution timestamp
+    pub resolved_at: Option<i64>,
+    
+    /// PDA bump
+    pub bump: u8,
+    
+    /// Lamports bonded by the challenger, returned if upheld and forfeited if dismissed
+    pub bond_lamports: u64,
+}
+
+impl ReviewChallenge {
+    pub fn space() -> usize {
+        8 + // discriminator
+        32 + // submission pubkey
+        32 + // validator pubkey
+        32 + // challenger pubkey
+        4 + QuestSubmission::MAX_REASON_LEN + // reason
+        1 + // status enum
+        8 + // slashed_lamports
+        8 + // created_at
+        1 + 8 + // resolved_at option
+        1 + // bump
+        8 // bond_lamports
+    }
+}
+
+/// Review challenge status
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum ChallengeStatus {
+    Open,
+    /// Validator slashed
+    Upheld,
+    Dismissed,
+}
+
+/// Program treasury collecting slashed stake and forfeits
+#[account]
+pub struct Treasury {
+    /// Total lamports collected
+    pub total_collected: u64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Treasury {
+    pub const LEN: usize = 8 + 8 + 1;
+}
+
+/// Vault funding per-review validator fees
+#[account]
+pub struct RewardVault {
+    /// Total lamports deposited
+    pub total_funded: u64,
+    
+    /// Total lamports paid out as review fees
+    pub total_paid: u64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl RewardVault {
+    pub const LEN: usize = 8 + 8 + 8 + 1;
+}
+
+/// A single validator's vote on a consensus submission
+#[account]
+pub struct SubmissionVote {
+    /// Submission voted on
+    pub submission: Pubkey,
+    
+    /// Voting validator
+    pub validator: Pubkey,
+    
+    /// Approve (true) or reject (false)
+    pub approve: bool,
+    
+    /// Vote timestamp
+    pub voted_at: i64,
+    
+    /// Whether the validator's reward share has been claimed
+    pub reward_claimed: bool,
+    
+    /// PDA bump
+    pub bump: u8,
+    
+    /// Submission attempt the vote was cast on
+    pub attempt_number: u8,
+    
+    /// Reject vote that also flags the submission as fraudulent
+    pub fraud: bool,
+}
+
+impl SubmissionVote {
+    pub fn space() -> usize {
+        8 + // discriminator
+        32 + // submission pubkey
+        32 + // validator pubkey
+        1 + // approve
+        8 + // voted_at
+        1 + // reward_claimed
+        1 + // bump
+        1 + // attempt_number
+        1 // fraud
+    }
 }
 
 /// User profile for progression tracking
@@ -263,10 +1114,35 @@ pub struct UserProfile {
     
     /// PDA bump
     pub bump: u8,
+    
+    /// IPFS CID of the avatar image (empty if unset)
+    pub avatar_cid: String,
+    
+    /// Short profile bio
+    pub bio: String,
+    
+    /// Last username change timestamp (for rename rate limiting)
+    pub username_changed_at: i64,
+    
+    /// Day index (unix days) of the sponsored submission counter
+    pub sponsored_day: i64,
+    
+    /// Relayer-sponsored submissions on `sponsored_day`
+    pub sponsored_today: u16,
+    
+    /// Submissions currently awaiting review
+    pub pending_submissions: u16,
+    
+    /// Timestamps of the most recent submissions (rolling 24h rate limit)
+    pub recent_submissions: [i64; UserProfile::SUBMISSION_HISTORY_LEN],
 }
 
 impl UserProfile {
     pub const MAX_USERNAME_LEN: usize = 32;
+    pub const MIN_USERNAME_LEN: usize = 3;
+    pub const MAX_AVATAR_CID_LEN: usize = QuestSubmission::MAX_IPFS_HASH_LEN;
+    pub const MAX_BIO_LEN: usize = 160;
+    pub const SUBMISSION_HISTORY_LEN: usize = 16;
     
     pub fn space() -> usize {
         8 + // discriminator
@@ -285,7 +1161,14 @@ impl UserProfile {
         8 + // last_quest_date
         16 + // achievements u128
         1 + // rank_tier enum
-        1 // bump
+        1 + // bump
+        4 + Self::MAX_AVATAR_CID_LEN + // avatar_cid
+        4 + Self::MAX_BIO_LEN + // bio
+        8 + // username_changed_at
+        8 + // sponsored_day
+        2 + // sponsored_today
+        2 + // pending_submissions
+        8 * Self::SUBMISSION_HISTORY_LEN // recent_submissions
     }
     
     /// Calculate level from XP
@@ -297,10 +1180,134 @@ impl UserProfile {
     pub fn xp_for_next_level(&self) -> u64 {
         ((self.level + 1) as u64) * 500
     }
+    
+    /// Submissions made within the 24h before `now`
+    pub fn submissions_in_last_day(&self, now: i64) -> usize {
+        self.recent_submissions
+            .iter()
+            .filter(|&&at| at > 0 && at > now - constants::ONE_DAY_SECONDS)
+            .count()
+    }
+    
+    /// Record a submission, overwriting the oldest history slot
+    pub fn record_submission(&mut self, now: i64) {
+        if let Some(oldest) = self.recent_submissions.iter_mut().min() {
+            *oldest = now;
+        }
+    }
+    
+    /// Release a pending slot once a submission is decided or expired
+    pub fn release_pending_submission(&mut self) {
+        self.pending_submissions = self.pending_submissions.saturating_sub(1);
+    }
 }
 
-/// User rank tiers
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+/// Handle registry entry, seeded by the normalized username so handles are unique
+#[account]
+pub struct UsernameRecord {
+    /// Profile owner holding this handle
+    pub owner: Pubkey,
+    
+    /// Username as displayed (original casing)
+    pub username: String,
+    
+    /// Claim timestamp
+    pub claimed_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl UsernameRecord {
+    pub fn space() -> usize {
+        8 + // discriminator
+        32 + // owner pubkey
+        4 + UserProfile::MAX_USERNAME_LEN + // username
+        8 + // claimed_at
+        1 // bump
+    }
+}
+
+/// Policy restricting which relayers may pay fees and rent on behalf of users
+#[account]
+pub struct SponsorPolicy {
+    /// Relayers allowed to act as fee payer for other users
+    pub relayers: Vec<Pubkey>,
+    
+    /// Maximum sponsored submissions per user per day
+    pub max_sponsored_per_day: u16,
+    
+    /// Last updated timestamp
+    pub updated_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl SponsorPolicy {
+    pub const MAX_RELAYERS: usize = 5;
+    
+    pub fn space() -> usize {
+        8 + // discriminator
+        4 + (Self::MAX_RELAYERS * 32) + // relayers vec
+        2 + // max_sponsored_per_day
+        8 + // updated_at
+        1 // bump
+    }
+    
+    pub fn is_relayer(&self, key: &Pubkey) -> bool {
+        self.relayers.contains(key)
+    }
+}
+
+/// Delegated signing key letting a mobile app submit on behalf of a profile owner
+#[account]
+pub struct SessionKey {
+    /// Profile owner that created the session
+    pub owner: Pubkey,
+    
+    /// Ephemeral key allowed to sign for the owner
+    pub session_key: Pubkey,
+    
+    /// Expiry timestamp
+    pub expires_at: i64,
+    
+    /// Allowed actions bitmask (see `SessionKey::SCOPE_*`)
+    pub scope: u8,
+    
+    /// Locations the session may submit for (empty = any location)
+    pub locations: Vec<String>,
+    
+    /// Creation timestamp
+    pub created_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl SessionKey {
+    pub const SCOPE_SUBMIT: u8 = 1 << 0;
+    pub const ALL_SCOPES: u8 = Self::SCOPE_SUBMIT;
+    pub const MAX_LOCATIONS: usize = 3;
+    
+    pub fn space() -> usize {
+        8 + // discriminator
+        32 + // owner pubkey
+        32 + // session_key pubkey
+        8 + // expires_at
+        1 + // scope
+        4 + Self::MAX_LOCATIONS * (4 + LocationQuests::MAX_LOCATION_LEN) + // locations vec
+        8 + // created_at
+        1 // bump
+    }
+    
+    pub fn allows_location(&self, location: &str) -> bool {
+        self.locations.is_empty() || self.locations.iter().any(|l| l == location)
+    }
+}
+
+/// User rank tiers, ordered lowest to highest
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, PartialOrd)]
 pub enum RankTier {
     Bronze,   // Levels 1-10
     Silver,   // Levels 11-25
@@ -342,10 +1349,13 @@ pub mod constants {
     pub const MAX_QUESTS_PER_LOCATION: usize = 10;
     
     /// Program version
-    pub const PROGRAM_VERSION: u8 = 1;
+    pub const PROGRAM_VERSION: u8 = 2;
     
     /// XP rewards
     pub const BASE_XP_REWARD: u64 = 100;
+    
+    /// Partial XP per approved trail checkpoint
+    pub const CHECKPOINT_XP_REWARD: u64 = 25;
     pub const DIFFICULTY_MULTIPLIER_MEDIUM: f64 = 1.5;
     pub const DIFFICULTY_MULTIPLIER_HARD: f64 = 2.0;
     
@@ -354,4 +1364,70 @@ pub mod constants {
     
     /// Max attempts per quest
     pub const MAX_QUEST_ATTEMPTS: u8 = 3;
+    
+    /// Seconds per day (for daily counters)
+    pub const ONE_DAY_SECONDS: i64 = 86_400;
+    
+    /// Default max distance between a submission and its quest
+    pub const DEFAULT_MAX_SUBMISSION_DISTANCE_M: u32 = 500;
+    
+    /// Default max accepted GPS accuracy radius
+    pub const DEFAULT_MAX_GPS_ACCURACY_M: u32 = 100;
+    
+    /// Max age of a GPS fix at submission time (1 hour)
+    pub const MAX_GPS_FIX_AGE_SECONDS: i64 = 3_600;
+    
+    /// Tolerated clock skew for client timestamps
+    pub const MAX_CLOCK_SKEW_SECONDS: i64 = 60;
+    
+    /// Default time allowed to appeal a rejection (7 days)
+    pub const DEFAULT_APPEAL_WINDOW_SECONDS: i64 = 7 * 86_400;
+    
+    /// Default minimum validator bond (1 SOL)
+    pub const DEFAULT_MIN_VALIDATOR_STAKE: u64 = 1_000_000_000;
+    
+    /// Default unbonding period (14 days, the challenge window)
+    pub const DEFAULT_UNBONDING_PERIOD_SECONDS: i64 = 14 * 86_400;
+    
+    /// How long after a decision it can be challenged (14 days)
+    pub const CHALLENGE_WINDOW_SECONDS: i64 = 14 * 86_400;
+    
+    /// Bond a challenger puts up to discourage frivolous challenges (0.01 SOL)
+    pub const CHALLENGE_BOND_LAMPORTS: u64 = 10_000_000;
+    
+    /// Default fee per reviewed submission (0.001 SOL)
+    pub const DEFAULT_REVIEW_FEE_LAMPORTS: u64 = 1_000_000;
+    
+    /// Default anti-spam submission deposit (0.005 SOL)
+    pub const DEFAULT_SUBMISSION_DEPOSIT_LAMPORTS: u64 = 5_000_000;
+    
+    /// Default submissions a user may have awaiting review
+    pub const DEFAULT_MAX_PENDING_SUBMISSIONS: u16 = 5;
+    
+    /// Default submissions per user per rolling 24h
+    pub const DEFAULT_MAX_SUBMISSIONS_PER_DAY: u16 = 10;
+    
+    /// Default time a submission may wait for review (7 days)
+    pub const DEFAULT_REVIEW_SLA_SECONDS: i64 = 7 * 86_400;
+    
+    /// Default paid reviews per validator per day
+    pub const DEFAULT_MAX_PAID_REVIEWS_PER_DAY: u16 = 50;
+    
+    /// Share of consensus rewards split between agreeing validators (10%)
+    pub const VALIDATOR_REWARD_SHARE_BPS: u64 = 1_000;
+    
+    /// Default minimum AI confidence for automated approval (90%)
+    pub const DEFAULT_MIN_AI_CONFIDENCE_BPS: u16 = 9_000;
+    
+    /// Basis point denominator
+    pub const BPS_DENOMINATOR: u64 = 10_000;
+    
+    /// Mean Earth radius used for distance calculations
+    pub const EARTH_RADIUS_M: i128 = 6_371_000;
+    
+    /// Maximum session key lifetime (7 days)
+    pub const MAX_SESSION_DURATION_SECONDS: i64 = 7 * 86_400;
+    
+    /// Minimum time between username changes (30 days)
+    pub const USERNAME_CHANGE_COOLDOWN_SECONDS: i64 = 30 * 86_400;
 }
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
 use crate::state::*;
 use crate::errors::XploraError;
 
@@ -47,6 +49,208 @@ pub fn validate_quest(quest: &Quest) -> Result<()> {
         XploraError::InvalidDifficulty
     );
     
+    // Validate schedule
+    require!(
+        quest.starts_at >= 0 && (quest.ends_at == 0 || quest.ends_at > quest.starts_at),
+        XploraError::InvalidQuestSchedule
+    );
+    
+    // Validate type-specific data
+    validate_quest_type(&quest.quest_type)?;
+    
+    // Check-in progress is not reset, so trails and scavenger hunts are one-shot
+    require!(
+        !quest.repeatable || quest.quest_type.waypoints().is_none(),
+        XploraError::InvalidRepeatSettings
+    );
+    
+    // Validate repeat settings
+    require!(
+        !quest.repeatable
+            || (quest.cooldown_seconds > 0
+                && quest.repeat_xp_bps as u64 <= constants::BPS_DENOMINATOR),
+        XploraError::InvalidRepeatSettings
+    );
+    
+    // Validate prerequisites
+    require!(
+        quest.prerequisites.len() <= Quest::MAX_PREREQUISITES,
+        XploraError::TooManyPrerequisites
+    );
+    
+    Ok(())
+}
+
+/// Validates that prerequisites within the same location reference another
+/// existing quest. Run once the quest's id has been assigned.
+pub fn validate_prerequisites(
+    quest: &Quest,
+    location_key: &Pubkey,
+    location_quests: &LocationQuests,
+) -> Result<()> {
+    for prerequisite in &quest.prerequisites {
+        if prerequisite.location_quests == *location_key {
+            require!(
+                prerequisite.quest_id != quest.id
+                    && location_quests.quest_by_id(prerequisite.quest_id).is_ok(),
+                XploraError::InvalidPrerequisite
+            );
+        }
+    }
+    
+    Ok(())
+}
+
+/// Validates the data carried by a quest type
+pub fn validate_quest_type(quest_type: &QuestType) -> Result<()> {
+    match quest_type {
+        QuestType::Discovery | QuestType::Exploration | QuestType::Challenge => {}
+        QuestType::Trail { waypoints } => {
+            require!(
+                waypoints.len() >= 2 && waypoints.len() <= QuestType::MAX_WAYPOINTS,
+                XploraError::InvalidQuestType
+            );
+        }
+        QuestType::Scavenger { landmarks, required } => {
+            require!(
+                !landmarks.is_empty() && landmarks.len() <= QuestType::MAX_WAYPOINTS,
+                XploraError::InvalidQuestType
+            );
+            require!(
+                *required > 0 && *required as usize <= landmarks.len(),
+                XploraError::InvalidQuestType
+            );
+        }
+        QuestType::Quiz { answer_hash, .. } => {
+            require!(*answer_hash != [0u8; 32], XploraError::InvalidQuestType);
+        }
+        QuestType::Social { required_participants } => {
+            require!(
+                *required_participants >= 2
+                    && *required_participants as usize <= QuestSubmission::MAX_PARTICIPANTS + 1,
+                XploraError::InvalidQuestType
+            );
+        }
+    }
+    
+    // Waypoints must lie within Nepal, like quest coordinates
+    for waypoint in quest_type.waypoints().unwrap_or_default() {
+        require!(
+            waypoint.latitude_e6 >= degrees_to_e6(constants::NEPAL_MIN_LATITUDE)
+                && waypoint.latitude_e6 <= degrees_to_e6(constants::NEPAL_MAX_LATITUDE)
+                && waypoint.longitude_e6 >= degrees_to_e6(constants::NEPAL_MIN_LONGITUDE)
+                && waypoint.longitude_e6 <= degrees_to_e6(constants::NEPAL_MAX_LONGITUDE),
+            XploraError::InvalidWaypoint
+        );
+    }
+    
+    Ok(())
+}
+
+/// Hash of a quiz answer: sha256(salt || trimmed, lowercased answer)
+pub fn quiz_answer_hash(salt: &[u8; 16], answer: &str) -> [u8; 32] {
+    let normalized = answer.trim().to_lowercase();
+    hashv(&[salt, normalized.as_bytes()]).to_bytes()
+}
+
+/// Commitment to a quiz answer: sha256(user || nonce || trimmed, lowercased answer).
+/// Binding the user stops others from replaying someone else's commitment.
+pub fn quiz_commitment(user: &Pubkey, nonce: &[u8; 32], answer: &str) -> [u8; 32] {
+    let normalized = answer.trim().to_lowercase();
+    hashv(&[user.as_ref(), nonce, normalized.as_bytes()]).to_bytes()
+}
+
+/// Verifies the type-specific requirements of a quest at submission time.
+/// Trails need every checkpoint approved and scavenger hunts enough approved
+/// landmarks. Quizzes are answered through commit-reveal instead.
+pub fn check_quest_type_completion(
+    quest: &Quest,
+    progress: Option<&QuestProgress>,
+) -> Result<()> {
+    // Progress must belong to this quest, not one since deleted at the same index
+    let progress = progress.filter(|progress| progress.quest_id == quest.id);
+    
+    match &quest.quest_type {
+        QuestType::Trail { waypoints } => {
+            let all_approved = progress
+                .map(|progress| progress.approved_count as usize == waypoints.len())
+                .unwrap_or(false);
+            require!(all_approved, XploraError::TrailIncomplete);
+        }
+        QuestType::Scavenger { required, .. } => {
+            let found = progress.map(|progress| progress.approved_count).unwrap_or(0);
+            require!(found >= *required, XploraError::NotEnoughLandmarks);
+        }
+        QuestType::Quiz { .. } => return err!(XploraError::QuizRevealRequired),
+        _ => {}
+    }
+    
+    Ok(())
+}
+
+/// Verifies a quest is open for submissions at `now`
+pub fn check_quest_active(quest: &Quest, now: i64) -> Result<()> {
+    match quest.quest_status(now) {
+        QuestStatus::Scheduled => err!(XploraError::QuestNotStarted),
+        QuestStatus::Expired => err!(XploraError::QuestEnded),
+        QuestStatus::Active => Ok(()),
+    }
+}
+
+/// Verifies a user meets a quest's level and rank tier requirements
+pub fn check_quest_eligibility(quest: &Quest, profile: &UserProfile) -> Result<()> {
+    require!(profile.level >= quest.min_level, XploraError::LevelTooLow);
+    
+    if let Some(min_rank_tier) = &quest.min_rank_tier {
+        require!(profile.rank_tier >= *min_rank_tier, XploraError::RankTierTooLow);
+    }
+    
+    Ok(())
+}
+
+/// Assigns the next stable id of a location to a new quest
+pub fn assign_quest_id(location_quests: &mut LocationQuests, quest: &mut Quest) -> Result<()> {
+    require!(
+        (location_quests.next_quest_id as usize) < LocationQuests::MAX_QUEST_IDS,
+        XploraError::QuestIdsExhausted
+    );
+    quest.id = location_quests.next_quest_id;
+    location_quests.next_quest_id += 1;
+    
+    Ok(())
+}
+
+/// Verifies a user has completed every prerequisite of a quest.
+/// Same-location prerequisites are read from `completions`; each other
+/// location needs its `QuestCompletions` PDA in `remaining_accounts`.
+pub fn check_prerequisites(
+    quest: &Quest,
+    user: &Pubkey,
+    location_quests: &Pubkey,
+    completions: &QuestCompletions,
+    remaining_accounts: &[AccountInfo],
+    program_id: &Pubkey,
+) -> Result<()> {
+    for prerequisite in &quest.prerequisites {
+        let completed = if prerequisite.location_quests == *location_quests {
+            completions.is_completed(prerequisite.quest_id)
+        } else {
+            let (expected, _) = Pubkey::find_program_address(
+                &[b"completions", user.as_ref(), prerequisite.location_quests.as_ref()],
+                program_id,
+            );
+            match remaining_accounts.iter().find(|info| info.key() == expected) {
+                Some(info) if info.owner == program_id => {
+                    let data = info.try_borrow_data()?;
+                    QuestCompletions::try_deserialize(&mut &data[..])?
+                        .is_completed(prerequisite.quest_id)
+                }
+                _ => false,
+            }
+        };
+        require!(completed, XploraError::PrerequisitesNotMet);
+    }
+    
     Ok(())
 }
 
@@ -60,6 +264,537 @@ pub fn validate_location(location: &str) -> Result<()> {
     Ok(())
 }
 
+/// Validates a username handle (ASCII letters, digits and underscores)
+pub fn validate_username(username: &str) -> Result<()> {
+    require!(
+        username.len() >= UserProfile::MIN_USERNAME_LEN
+            && username.len() <= UserProfile::MAX_USERNAME_LEN
+            && username.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_'),
+        XploraError::InvalidUsername
+    );
+    
+    Ok(())
+}
+
+/// Normalizes a username for the handle registry so lookups are case-insensitive
+pub fn normalize_username(username: &str) -> String {
+    username.to_ascii_lowercase()
+}
+
+/// Multicodecs accepted as CIDv1 content types (raw, dag-pb, dag-cbor, dag-json)
+const CID_CODECS: [u64; 4] = [0x55, 0x70, 0x71, 0x0129];
+
+/// Multihash functions accepted in CIDs with their digest lengths
+const CID_MULTIHASHES: [(u64, usize); 9] = [
+    (0x12, 32),   // sha2-256
+    (0x13, 64),   // sha2-512
+    (0x14, 64),   // sha3-512
+    (0x16, 32),   // sha3-256
+    (0x1b, 32),   // keccak-256
+    (0x1e, 32),   // blake3
+    (0xb220, 32), // blake2b-256
+    (0xb240, 64), // blake2b-512
+    (0xb260, 32), // blake2s-256
+];
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Parses an IPFS content identifier and returns its binary form.
+///
+/// Accepts CIDv0 (base58btc `Qm...`) and CIDv1 in multibase base32 (`b...`)
+/// or base58btc (`z...`). CIDv0 is returned as its bare multihash, CIDv1 as
+/// `<version><codec><multihash>`, which keeps both forms distinguishable.
+pub fn parse_cid(cid: &str) -> Result<Vec<u8>> {
+    require!(
+        !cid.is_empty() && cid.len() <= QuestSubmission::MAX_IPFS_HASH_LEN,
+        XploraError::InvalidIpfsHash
+    );
+    
+    let bytes = if cid.len() == 46 && cid.starts_with("Qm") {
+        // CIDv0 is a bare sha2-256 multihash
+        let multihash = decode_base58(cid.as_bytes())?;
+        require!(
+            multihash.len() == 34 && multihash[0] == 0x12 && multihash[1] == 0x20,
+            XploraError::InvalidIpfsHash
+        );
+        multihash
+    } else {
+        let (prefix, encoded) = cid.as_bytes().split_at(1);
+        let bytes = match prefix[0] {
+            b'b' => decode_base32(encoded)?,
+            b'z' => decode_base58(encoded)?,
+            _ => return err!(XploraError::InvalidIpfsHash),
+        };
+        
+        let (version, rest) = read_varint(&bytes)?;
+        require!(version == 1, XploraError::InvalidIpfsHash);
+        let (codec, multihash) = read_varint(rest)?;
+        require!(CID_CODECS.contains(&codec), XploraError::InvalidIpfsHash);
+        validate_multihash(multihash)?;
+        bytes
+    };
+    
+    require!(bytes.len() <= QuestSubmission::MAX_CID_BYTES, XploraError::InvalidIpfsHash);
+    
+    Ok(bytes)
+}
+
+/// Validates an IPFS content identifier
+pub fn validate_ipfs_hash(ipfs_hash: &str) -> Result<()> {
+    parse_cid(ipfs_hash).map(|_| ())
+}
+
+/// Validates evidence attachments and converts them to their stored form
+pub fn validate_evidence(attachments: &[EvidenceAttachment]) -> Result<Vec<Evidence>> {
+    require!(
+        !attachments.is_empty() && attachments.len() <= QuestSubmission::MAX_EVIDENCE_ITEMS,
+        XploraError::InvalidEvidenceCount
+    );
+    
+    let mut evidence: Vec<Evidence> = Vec::with_capacity(attachments.len());
+    for attachment in attachments {
+        let cid = parse_cid(&attachment.cid)?;
+        
+        // Content hash must be set and the same file cannot be attached twice
+        require!(
+            attachment.content_hash != [0u8; 32]
+                && !evidence.iter().any(|e| e.cid == cid || e.content_hash == attachment.content_hash),
+            XploraError::InvalidEvidence
+        );
+        
+        evidence.push(Evidence {
+            cid,
+            media_type: attachment.media_type.clone(),
+            content_hash: attachment.content_hash,
+        });
+    }
+    
+    Ok(evidence)
+}
+
+/// Checks a multihash uses a known hash function with a matching digest length
+fn validate_multihash(multihash: &[u8]) -> Result<()> {
+    let (code, rest) = read_varint(multihash)?;
+    let (length, digest) = read_varint(rest)?;
+    
+    let expected_len = CID_MULTIHASHES
+        .iter()
+        .find(|(known, _)| *known == code)
+        .map(|(_, len)| *len)
+        .ok_or(XploraError::InvalidIpfsHash)?;
+    require!(
+        length as usize == expected_len && digest.len() == expected_len,
+        XploraError::InvalidIpfsHash
+    );
+    
+    Ok(())
+}
+
+/// Reads an unsigned LEB128 varint, returning the value and the remaining bytes
+fn read_varint(bytes: &[u8]) -> Result<(u64, &[u8])> {
+    let mut value: u64 = 0;
+    for (i, byte) in bytes.iter().enumerate().take(9) {
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, &bytes[i + 1..]));
+        }
+    }
+    
+    err!(XploraError::InvalidIpfsHash)
+}
+
+/// Decodes a base58btc string
+fn decode_base58(input: &[u8]) -> Result<Vec<u8>> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(input.len());
+    for c in input {
+        let mut carry = BASE58_ALPHABET
+            .iter()
+            .position(|a| a == c)
+            .ok_or(XploraError::InvalidIpfsHash)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    
+    // Leading '1's encode leading zero bytes
+    let leading_zeros = input.iter().take_while(|c| **c == b'1').count();
+    bytes.extend(core::iter::repeat_n(0, leading_zeros));
+    bytes.reverse();
+    
+    Ok(bytes)
+}
+
+/// Decodes unpadded lowercase RFC 4648 base32
+fn decode_base32(input: &[u8]) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(input.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits: u32 = 0;
+    for c in input {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|a| a == c)
+            .ok_or(XploraError::InvalidIpfsHash)? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    
+    // Trailing bits must be zero padding
+    require!(bits < 5 && buffer == 0, XploraError::InvalidIpfsHash);
+    
+    Ok(bytes)
+}
+
+/// Fixed-point scale used by the geo helpers (1.0 == 10^18)
+const FP_SCALE: i128 = 1_000_000_000_000_000_000;
+
+/// Pi in fixed point
+const FP_PI: i128 = 3_141_592_653_589_793_238;
+
+/// Converts degrees to fixed-point microdegrees
+pub fn degrees_to_e6(degrees: f64) -> i32 {
+    (degrees * 1_000_000.0) as i32
+}
+
+/// Validates a claimed GPS fix against the quest coordinates and returns the
+/// distance between them in meters
+pub fn validate_location_proof(
+    proof: &LocationProof,
+    quest: &Quest,
+    config: &RegistryConfig,
+    now: i64,
+) -> Result<u32> {
+    let target = Waypoint {
+        latitude_e6: degrees_to_e6(quest.latitude),
+        longitude_e6: degrees_to_e6(quest.longitude),
+    };
+    validate_location_fix(proof, &target, config, now)
+}
+
+/// Validates a claimed GPS fix against a target point and returns the
+/// distance between them in meters
+pub fn validate_location_fix(
+    proof: &LocationProof,
+    target: &Waypoint,
+    config: &RegistryConfig,
+    now: i64,
+) -> Result<u32> {
+    // Claimed position must be within Nepal, like quest coordinates
+    require!(
+        proof.latitude_e6 >= degrees_to_e6(constants::NEPAL_MIN_LATITUDE)
+            && proof.latitude_e6 <= degrees_to_e6(constants::NEPAL_MAX_LATITUDE),
+        XploraError::InvalidLatitude
+    );
+    require!(
+        proof.longitude_e6 >= degrees_to_e6(constants::NEPAL_MIN_LONGITUDE)
+            && proof.longitude_e6 <= degrees_to_e6(constants::NEPAL_MAX_LONGITUDE),
+        XploraError::InvalidLongitude
+    );
+    
+    require!(
+        proof.accuracy_m <= config.max_gps_accuracy_m,
+        XploraError::GpsAccuracyTooLow
+    );
+    // Fix must be recent, allowing for a device clock slightly ahead of ours
+    let fix_age = now.checked_sub(proof.captured_at)
+        .ok_or(XploraError::StaleLocationFix)?;
+    require!(
+        (-constants::MAX_CLOCK_SKEW_SECONDS..=constants::MAX_GPS_FIX_AGE_SECONDS).contains(&fix_age),
+        XploraError::StaleLocationFix
+    );
+    
+    let distance_m = haversine_distance_m(
+        proof.latitude_e6,
+        proof.longitude_e6,
+        target.latitude_e6,
+        target.longitude_e6,
+    );
+    require!(
+        config.max_submission_distance_m == 0 || distance_m <= config.max_submission_distance_m,
+        XploraError::LocationTooFar
+    );
+    
+    Ok(distance_m)
+}
+
+/// Great-circle distance in meters between two points given in microdegrees,
+/// computed with the haversine formula in fixed-point arithmetic
+pub fn haversine_distance_m(lat1_e6: i32, lon1_e6: i32, lat2_e6: i32, lon2_e6: i32) -> u32 {
+    let lat1 = e6_to_radians_fp(lat1_e6 as i128);
+    let lat2 = e6_to_radians_fp(lat2_e6 as i128);
+    let half_dlat = e6_to_radians_fp(lat2_e6 as i128 - lat1_e6 as i128) / 2;
+    let half_dlon = e6_to_radians_fp(lon2_e6 as i128 - lon1_e6 as i128) / 2;
+    
+    // a = sin²(Δφ/2) + cos φ1 · cos φ2 · sin²(Δλ/2)
+    let sin_dlat = sin_fp(half_dlat);
+    let sin_dlon = sin_fp(half_dlon);
+    let a = fp_mul(sin_dlat, sin_dlat)
+        + fp_mul(fp_mul(cos_fp(lat1), cos_fp(lat2)), fp_mul(sin_dlon, sin_dlon));
+    let a = a.clamp(0, FP_SCALE);
+    
+    // c = 2 · asin(√a)
+    let c = 2 * asin_fp(sqrt_fp(a));
+    
+    (constants::EARTH_RADIUS_M * c / FP_SCALE) as u32
+}
+
+fn e6_to_radians_fp(degrees_e6: i128) -> i128 {
+    degrees_e6 * FP_PI / 180_000_000
+}
+
+fn fp_mul(a: i128, b: i128) -> i128 {
+    a * b / FP_SCALE
+}
+
+/// Taylor series sine, valid for |x| <= pi
+fn sin_fp(x: i128) -> i128 {
+    let x2 = fp_mul(x, x);
+    let mut term = x;
+    let mut sum = x;
+    for n in 1..=12 {
+        term = -fp_mul(term, x2) / ((2 * n) * (2 * n + 1));
+        sum += term;
+    }
+    sum
+}
+
+/// Taylor series cosine, valid for |x| <= pi
+fn cos_fp(x: i128) -> i128 {
+    let x2 = fp_mul(x, x);
+    let mut term = FP_SCALE;
+    let mut sum = FP_SCALE;
+    for n in 1..=12 {
+        term = -fp_mul(term, x2) / ((2 * n - 1) * (2 * n));
+        sum += term;
+    }
+    sum
+}
+
+/// Square root of a fixed-point value in [0, 1]
+fn sqrt_fp(x: i128) -> i128 {
+    let target = x * FP_SCALE;
+    if target == 0 {
+        return 0;
+    }
+    
+    // Newton's method on integers
+    let mut guess = target;
+    let mut next = (guess + 1) / 2;
+    while next < guess {
+        guess = next;
+        next = (guess + target / guess) / 2;
+    }
+    guess
+}
+
+/// Arcsine of a fixed-point value in [0, 1]
+fn asin_fp(x: i128) -> i128 {
+    if x > FP_SCALE / 2 {
+        // asin(x) = pi/2 - 2 asin(sqrt((1 - x) / 2)) keeps the series argument <= 0.5
+        return FP_PI / 2 - 2 * asin_fp(sqrt_fp((FP_SCALE - x) / 2));
+    }
+    
+    let x2 = fp_mul(x, x);
+    let mut power = x;
+    let mut coefficient = FP_SCALE;
+    let mut sum = x;
+    for n in 1..=24 {
+        // coefficient_n = (2n)! / (4^n (n!)^2), term = coefficient_n * x^(2n+1) / (2n+1)
+        coefficient = coefficient * (2 * n - 1) / (2 * n);
+        power = fp_mul(power, x2);
+        sum += fp_mul(coefficient, power) / (2 * n + 1);
+    }
+    sum
+}
+
+/// Signed message extracted from an Ed25519 sigverify instruction
+pub struct Ed25519Attestation {
+    pub signer: Pubkey,
+    pub message: Vec<u8>,
+}
+
+/// Loads the Ed25519 sigverify instruction immediately preceding the current
+/// one, if any. The runtime has already verified the signature; this only
+/// extracts which key signed which message.
+pub fn load_ed25519_attestation(instructions_sysvar: &AccountInfo) -> Result<Option<Ed25519Attestation>> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    if current_index == 0 {
+        return Ok(None);
+    }
+    
+    let ix = load_instruction_at_checked(current_index as usize - 1, instructions_sysvar)?;
+    if ix.program_id != solana_sdk_ids::ed25519_program::ID {
+        return Ok(None);
+    }
+    
+    // Header: num_signatures (1), padding (1), then one 14-byte offsets struct
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, XploraError::InvalidAttestation);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let public_key_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    
+    // Signature, key and message must all live in the sigverify instruction itself
+    require!(
+        read_u16(4) == u16::MAX && read_u16(8) == u16::MAX && read_u16(14) == u16::MAX,
+        XploraError::InvalidAttestation
+    );
+    require!(
+        data.len() >= public_key_offset + 32 && data.len() >= message_offset + message_size,
+        XploraError::InvalidAttestation
+    );
+    
+    let signer = Pubkey::try_from(&data[public_key_offset..public_key_offset + 32])
+        .map_err(|_| XploraError::InvalidAttestation)?;
+    
+    Ok(Some(Ed25519Attestation {
+        signer,
+        message: data[message_offset..message_offset + message_size].to_vec(),
+    }))
+}
+
+/// Checks a preceding Ed25519 instruction, if any, is the location oracle's
+/// attestation of `proof`. Returns whether the fix was attested.
+pub fn verify_location_attestation(
+    instructions_sysvar: &AccountInfo,
+    proof: &LocationProof,
+    user: &Pubkey,
+    location_quests: &Pubkey,
+    quest_index: u8,
+    oracle: &Pubkey,
+) -> Result<bool> {
+    match load_ed25519_attestation(instructions_sysvar)? {
+        Some(attestation) => {
+            let expected_message = proof.attestation_message(user, location_quests, quest_index);
+            require!(
+                *oracle != Pubkey::default()
+                    && attestation.signer == *oracle
+                    && attestation.message == expected_message,
+                XploraError::InvalidAttestation
+            );
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Checks whether a transaction is sponsored by a relayer and that the
+/// relayer is allowed by the sponsor policy. Returns true when sponsored.
+pub fn check_sponsorship(
+    sponsor_policy: Option<&SponsorPolicy>,
+    fee_payer: &Pubkey,
+    signer: &Pubkey,
+) -> Result<bool> {
+    if fee_payer == signer {
+        return Ok(false);
+    }
+    
+    let policy = sponsor_policy.ok_or(XploraError::RelayerNotAllowed)?;
+    require!(policy.is_relayer(fee_payer), XploraError::RelayerNotAllowed);
+    
+    Ok(true)
+}
+
+/// Enforces the daily sponsored submission limit for a sponsored submission
+pub fn record_sponsored_submission(
+    profile: &mut UserProfile,
+    sponsor_policy: Option<&SponsorPolicy>,
+    now: i64,
+) -> Result<()> {
+    let max_per_day = sponsor_policy
+        .map(|policy| policy.max_sponsored_per_day)
+        .unwrap_or_default();
+    let today = now / constants::ONE_DAY_SECONDS;
+    if profile.sponsored_day != today {
+        profile.sponsored_day = today;
+        profile.sponsored_today = 0;
+    }
+    require!(
+        profile.sponsored_today < max_per_day,
+        XploraError::SponsoredLimitReached
+    );
+    profile.sponsored_today += 1;
+    
+    Ok(())
+}
+
+/// Enforces the pending and rolling-24h submission limits and records the
+/// new submission in the profile's daily window
+pub fn record_submission_attempt(
+    profile: &mut UserProfile,
+    config: &RegistryConfig,
+    now: i64,
+) -> Result<()> {
+    require!(
+        config.max_pending_submissions == 0
+            || profile.pending_submissions < config.max_pending_submissions,
+        XploraError::TooManyPendingSubmissions
+    );
+    require!(
+        config.max_submissions_per_day == 0
+            || profile.submissions_in_last_day(now) < config.max_submissions_per_day as usize,
+        XploraError::DailySubmissionLimitReached
+    );
+    profile.record_submission(now);
+    
+    Ok(())
+}
+
+/// Verifies the signer may act for `user`: either the user itself or an
+/// unexpired session key with the required scope and location
+pub fn verify_user_signer(
+    user: &Pubkey,
+    signer: &Pubkey,
+    session_key: Option<&SessionKey>,
+    scope: u8,
+    location: &str,
+    now: i64,
+) -> Result<()> {
+    if user == signer {
+        return Ok(());
+    }
+    
+    let session = session_key.ok_or(XploraError::InvalidSessionKey)?;
+    require!(
+        session.owner == *user && session.session_key == *signer,
+        XploraError::InvalidSessionKey
+    );
+    require!(now < session.expires_at, XploraError::SessionExpired);
+    require!(session.scope & scope == scope, XploraError::SessionScopeNotAllowed);
+    require!(
+        session.allows_location(location),
+        XploraError::SessionLocationNotAllowed
+    );
+    
+    Ok(())
+}
+
+/// Moves lamports out of a program-owned account
+pub fn transfer_lamports_from_pda(
+    from: &AccountInfo,
+    to: &AccountInfo,
+    amount: u64,
+) -> Result<()> {
+    let from_balance = from.lamports().checked_sub(amount).ok_or(XploraError::Overflow)?;
+    let to_balance = to.lamports().checked_add(amount).ok_or(XploraError::Overflow)?;
+    **from.try_borrow_mut_lamports()? = from_balance;
+    **to.try_borrow_mut_lamports()? = to_balance;
+    
+    Ok(())
+}
+
 /// Gets current timestamp
 pub fn get_current_timestamp() -> i64 {
     Clock::get().unwrap().unix_timestamp
@@ -80,3 +815,128 @@ pub fn derive_location_quests_pda(
 pub fn derive_quest_registry_pda(program_id: &Pubkey) -> Result<(Pubkey, u8)> {
     Ok(Pubkey::find_program_address(&[b"quest_registry"], program_id))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// sha2-256("xplora"), the digest behind the CID fixtures below
+    const DIGEST_PREFIX: [u8; 4] = [79, 73, 70, 228];
+    
+    #[test]
+    fn parse_cid_accepts_cid_v0() {
+        let bytes = parse_cid("QmTg9ixHFktpKZTzFayxyGB93FWwtYoVKfbUWY1ChKBiNA").unwrap();
+        assert_eq!(bytes.len(), 34);
+        assert_eq!(bytes[..2], [0x12, 0x20]);
+        assert_eq!(bytes[2..6], DIGEST_PREFIX);
+    }
+    
+    #[test]
+    fn parse_cid_accepts_cid_v1_in_base32_and_base58() {
+        let base32 = parse_cid("bafybeicpjfdojjuivi5stguve4v3gsz3mwfftqrppfgk2lfjp3pkylnc74").unwrap();
+        assert_eq!(base32.len(), 36);
+        assert_eq!(base32[..4], [0x01, 0x70, 0x12, 0x20]);
+        assert_eq!(base32[4..8], DIGEST_PREFIX);
+        
+        let base58 = parse_cid("zdj7WamQ6PM7ENRCGmuzKwaqe1hfTiPc6DdBz11BLPsWkkcY6").unwrap();
+        assert_eq!(base58, base32);
+    }
+    
+    #[test]
+    fn parse_cid_keeps_the_codec() {
+        let raw = parse_cid("bafkreicpjfdojjuivi5stguve4v3gsz3mwfftqrppfgk2lfjp3pkylnc74").unwrap();
+        assert_eq!(raw[..2], [0x01, 0x55]);
+    }
+    
+    #[test]
+    fn parse_cid_rejects_malformed_identifiers() {
+        let invalid = [
+            "",
+            "Qm                                            ",
+            "QmTg9ixHFktpKZTzFayxyGB93FWwtYoVKfbUWY1ChKBiN0",
+            // Digest one byte short of its declared length
+            "bafybeicpjfdojjuivi5stguve4v3gsz3mwfftqrppfgk2lfjp3pkylnc",
+            // Unknown codec 0x99
+            "bagmqceraj5eunzfgrcvdwkm2sutsxm2lhnsyuwocf54uzljmvf7n5lbnul7q",
+            // Unsupported multibase prefix
+            "fbafybeicpjfdojjuivi5stguve4v3gsz3mwfftqrppfgk2lfjp3pkylnc74",
+        ];
+        for cid in invalid {
+            assert_eq!(parse_cid(cid), Err(XploraError::InvalidIpfsHash.into()), "{cid:?}");
+        }
+        
+        let overlong = format!("b{}", "a".repeat(QuestSubmission::MAX_IPFS_HASH_LEN));
+        assert_eq!(parse_cid(&overlong), Err(XploraError::InvalidIpfsHash.into()));
+    }
+    
+    /// Asserts a distance is within `tolerance_m` of the floating-point haversine
+    fn assert_distance(actual: u32, expected: f64, tolerance_m: f64) {
+        assert!(
+            (actual as f64 - expected).abs() <= tolerance_m,
+            "{actual}m, expected {expected}m"
+        );
+    }
+    
+    #[test]
+    fn haversine_is_zero_for_the_same_point() {
+        assert_eq!(haversine_distance_m(27_704_500, 85_307_700, 27_704_500, 85_307_700), 0);
+    }
+    
+    #[test]
+    fn haversine_matches_one_degree_of_latitude() {
+        let distance = haversine_distance_m(27_000_000, 85_000_000, 28_000_000, 85_000_000);
+        assert_distance(distance, 111_194.93, 2.0);
+    }
+    
+    #[test]
+    fn haversine_matches_known_distances() {
+        // Kathmandu Durbar Square to Bhaktapur Durbar Square
+        let durbar_squares = haversine_distance_m(27_704_500, 85_307_700, 27_672_200, 85_427_900);
+        assert_distance(durbar_squares, 12_368.08, 2.0);
+        
+        // Across the country, Janakpur to the far west
+        let across = haversine_distance_m(26_730_700, 85_925_200, 30_000_000, 81_000_000);
+        assert_distance(across, 603_518.15, 60.0);
+    }
+    
+    #[test]
+    fn haversine_is_symmetric() {
+        assert_eq!(
+            haversine_distance_m(27_704_500, 85_307_700, 27_672_200, 85_427_900),
+            haversine_distance_m(27_672_200, 85_427_900, 27_704_500, 85_307_700)
+        );
+    }
+    
+    #[test]
+    fn location_fix_age_is_bounded_both_ways() {
+        let target = Waypoint { latitude_e6: 27_704_500, longitude_e6: 85_307_700 };
+        let config = RegistryConfig::default();
+        let now = 1_700_000_000;
+        let fix = |captured_at| LocationProof {
+            latitude_e6: target.latitude_e6,
+            longitude_e6: target.longitude_e6,
+            accuracy_m: 10,
+            captured_at,
+        };
+        
+        for captured_at in [
+            now,
+            now - constants::MAX_GPS_FIX_AGE_SECONDS,
+            now + constants::MAX_CLOCK_SKEW_SECONDS,
+        ] {
+            assert_eq!(validate_location_fix(&fix(captured_at), &target, &config, now), Ok(0));
+        }
+        for captured_at in [
+            now - constants::MAX_GPS_FIX_AGE_SECONDS - 1,
+            now + constants::MAX_CLOCK_SKEW_SECONDS + 1,
+            i64::MIN,
+            i64::MAX,
+        ] {
+            assert_eq!(
+                validate_location_fix(&fix(captured_at), &target, &config, now),
+                Err(XploraError::StaleLocationFix.into()),
+                "{captured_at}"
+            );
+        }
+    }
+}